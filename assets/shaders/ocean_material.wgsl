@@ -17,8 +17,6 @@
 // seems to be be applied using the provided mesh function
 @vertex
 fn vertex(in: Vertex, @builtin(vertex_index) vertex_index : u32) -> VertexOutput {
-    let time = ocean_material_bindings::time.elapsed_seconds * ocean_material_bindings::settings.time_scale;
-
     var out: VertexOutput;
     var next_position = in.position;
 
@@ -38,17 +36,17 @@ fn vertex(in: Vertex, @builtin(vertex_index) vertex_index : u32) -> VertexOutput
         next_position += water_dynamics::gerstner_wave(
             ocean_material_bindings::settings.waves[i],
             in.position + ocean_material_bindings::position.center_offset + ocean_material_bindings::settings.tile_offset,
-            time
+            ocean_material_bindings::wave_phases.phases[i]
         );
         next_position_cw += water_dynamics::gerstner_wave(
             ocean_material_bindings::settings.waves[i],
             position_cw + ocean_material_bindings::position.center_offset + ocean_material_bindings::settings.tile_offset,
-            time
+            ocean_material_bindings::wave_phases.phases[i]
         );
         next_position_ccw += water_dynamics::gerstner_wave(
             ocean_material_bindings::settings.waves[i],
             position_ccw + ocean_material_bindings::position.center_offset + ocean_material_bindings::settings.tile_offset,
-            time
+            ocean_material_bindings::wave_phases.phases[i]
         );
     }
 
@@ -59,8 +57,7 @@ fn vertex(in: Vertex, @builtin(vertex_index) vertex_index : u32) -> VertexOutput
                 in.position,
                 ocean_material_bindings::settings.subdivision_count,
                 ocean_material_bindings::settings.quad_cell_size,
-                next_position,
-                time
+                next_position
             );
         }
         case 1u: {
@@ -7,7 +7,6 @@ struct OceanTilelSettings {
     tile_size: f32,
     quad_cell_size: f32,
     tier: u32,
-    time_scale: f32,
     waves: array<vec4<f32>, WAVES_COUNT>,
     subdivision_count: u32,
 }
@@ -16,9 +15,8 @@ struct OceanPosition {
     center_offset: vec3<f32>,
 }
 
-struct RollbackTime {
-    elapsed_seconds: f32,
-    padding: vec3<f32>,
+struct WavePhases {
+    phases: vec4<f32>,
 }
 
 @group(2) @binding(100)
@@ -28,4 +26,4 @@ var<uniform> settings: OceanTilelSettings;
 var<uniform> position: OceanPosition;
 
 @group(2) @binding(102)
-var<uniform> time: RollbackTime;
\ No newline at end of file
+var<uniform> wave_phases: WavePhases;
\ No newline at end of file
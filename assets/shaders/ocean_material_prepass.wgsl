@@ -14,8 +14,6 @@
 
 @vertex
 fn vertex(in: Vertex) -> VertexOutput {
-    let time = ocean_material_bindings::time.elapsed_seconds * ocean_material_bindings::settings.time_scale;
-
     var out: VertexOutput;
     var next_position = in.position;
 
@@ -23,7 +21,7 @@ fn vertex(in: Vertex) -> VertexOutput {
         next_position += gerstner_wave(
             ocean_material_bindings::settings.waves[i],
             in.position + ocean_material_bindings::position.center_offset + ocean_material_bindings::settings.tile_offset,
-            time
+            ocean_material_bindings::wave_phases.phases[i]
         );
     }
 
@@ -15,7 +15,7 @@ fn get_midpoint(a: vec3<f32>, b: vec3<f32>) -> vec3<f32> {
     );
 }
 
-fn get_wave_adjusted_midpoint(a: vec3<f32>, b: vec3<f32>, time: f32) -> vec3<f32> {
+fn get_wave_adjusted_midpoint(a: vec3<f32>, b: vec3<f32>) -> vec3<f32> {
     var grid_point_a = a;
     var grid_point_b = b;
 
@@ -23,13 +23,13 @@ fn get_wave_adjusted_midpoint(a: vec3<f32>, b: vec3<f32>, time: f32) -> vec3<f32
         grid_point_a += water_dynamics::gerstner_wave(
             ocean_material_bindings::settings.waves[i],
             a + ocean_material_bindings::position.center_offset + ocean_material_bindings::settings.tile_offset,
-            time
+            ocean_material_bindings::wave_phases.phases[i]
         );
 
         grid_point_b += water_dynamics::gerstner_wave(
             ocean_material_bindings::settings.waves[i],
             b + ocean_material_bindings::position.center_offset + ocean_material_bindings::settings.tile_offset,
-            time
+            ocean_material_bindings::wave_phases.phases[i]
         );
     }
 
@@ -113,8 +113,7 @@ fn smoothen_edges(
     position: vec3<f32>,
     subdivision_count: u32,
     quad_cell_size: f32,
-    default_position: vec3<f32>,
-    time: f32
+    default_position: vec3<f32>
 ) -> vec3<f32> {
     let tile_size = subdivision_count + 1u;
     let row = vertex_index / (tile_size * VERTICES_PER_QUAD);
@@ -127,16 +126,14 @@ fn smoothen_edges(
             if order == 1u || order == 5u {
                 return get_wave_adjusted_midpoint(
                     get_grid_point_west(position, quad_cell_size),
-                    get_grid_point_east(position, quad_cell_size),
-                    time
+                    get_grid_point_east(position, quad_cell_size)
                 );
             }
         } else { // Odd col
             if order == 3u {
                  return get_wave_adjusted_midpoint(
                     get_grid_point_west(position, quad_cell_size),
-                    get_grid_point_east(position, quad_cell_size),
-                    time
+                    get_grid_point_east(position, quad_cell_size)
                  );
             }
         }
@@ -147,16 +144,14 @@ fn smoothen_edges(
             if order == 0u {
                 return get_wave_adjusted_midpoint(
                     get_grid_point_west(position, quad_cell_size),
-                    get_grid_point_east(position, quad_cell_size),
-                    time
+                    get_grid_point_east(position, quad_cell_size)
                 );
             }
         } else { // Odd col
             if order == 2u || order == 4u {
                 return get_wave_adjusted_midpoint(
                     get_grid_point_west(position, quad_cell_size),
-                    get_grid_point_east(position, quad_cell_size),
-                    time
+                    get_grid_point_east(position, quad_cell_size)
                 );
             }
         }
@@ -168,16 +163,14 @@ fn smoothen_edges(
             if order == 2u || order == 4u {
                 return get_wave_adjusted_midpoint(
                     get_grid_point_north(position, quad_cell_size),
-                    get_grid_point_south(position, quad_cell_size),
-                    time
+                    get_grid_point_south(position, quad_cell_size)
                 );
             }
         } else { // Odd row
             if order == 3u {
                 return get_wave_adjusted_midpoint(
                     get_grid_point_north(position, quad_cell_size),
-                    get_grid_point_south(position, quad_cell_size),
-                    time
+                    get_grid_point_south(position, quad_cell_size)
                 );
             }
         }
@@ -188,16 +181,14 @@ fn smoothen_edges(
             if order == 0u {
                 return get_wave_adjusted_midpoint(
                     get_grid_point_north(position, quad_cell_size),
-                    get_grid_point_south(position, quad_cell_size),
-                    time
+                    get_grid_point_south(position, quad_cell_size)
                 );
             }
         } else { // Odd row
             if order == 1u || order == 5u {
                 return get_wave_adjusted_midpoint(
                     get_grid_point_north(position, quad_cell_size),
-                    get_grid_point_south(position, quad_cell_size),
-                    time
+                    get_grid_point_south(position, quad_cell_size)
                 );
             }
         }
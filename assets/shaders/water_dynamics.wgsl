@@ -4,14 +4,14 @@ const PI: f32 = 3.14159265358979323846264338327950288;
 const GRAVITY: f32 = 10.;
 
 // `wave`: Vec4 containing direction x, direction z, steepness, wave_length
-fn gerstner_wave(wave: vec4<f32>, p: vec3<f32>, time: f32) -> vec3<f32> {
+// `phase`: How far the wave has travelled, accumulated by the simulation
+fn gerstner_wave(wave: vec4<f32>, p: vec3<f32>, phase: f32) -> vec3<f32> {
     let steepness = wave.z;
     let wave_length = wave.w;
 
    let k: f32 = 2. * PI / wave_length;
-   let c: f32 = sqrt(GRAVITY / k);
    let d: vec2<f32> = normalize(wave.xy);
-   let f: f32 = k * (dot(d, p.xz) - c * time);
+   let f: f32 = k * dot(d, p.xz) - phase;
    let a: f32 = steepness / k;
 
     return vec3<f32>(
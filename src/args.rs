@@ -10,9 +10,65 @@ pub struct ArgsPlugin;
 
 impl Plugin for ArgsPlugin {
     fn build(&self, app: &mut App) {
-        let args = Args::parse();
+        let args = parse_args();
         info!("{args:?}");
 
         app.insert_resource(args);
     }
 }
+
+pub fn parse_args() -> Args {
+    let mut args = Args::parse();
+
+    #[cfg(target_arch = "wasm32")]
+    apply_query_string(&mut args);
+
+    // Spectators always watch an online session
+    if args.spectate {
+        args.sync_test = false;
+    }
+
+    args
+}
+
+// Browser builds have no command line, so online play is configured from the page url instead,
+// for example ?room=my_room&players=2&server=wss://example.com&hull=raft, adding
+// &spectators=1&spectate to watch, or &wind_waves to raise the waves from the wind
+#[cfg(target_arch = "wasm32")]
+fn apply_query_string(args: &mut Args) {
+    let Some(search) = web_sys::window().and_then(|window| window.location().search().ok()) else {
+        return;
+    };
+
+    let Ok(params) = web_sys::UrlSearchParams::new_with_str(&search) else {
+        return;
+    };
+
+    if let Some(server_url) = params.get("server") {
+        args.matchbox_server_url = server_url;
+    }
+
+    if let Some(num_players) = params.get("players").and_then(|value| value.parse().ok()) {
+        args.num_players = num_players;
+    }
+
+    if let Some(num_spectators) = params
+        .get("spectators")
+        .and_then(|value| value.parse().ok())
+    {
+        args.num_spectators = num_spectators;
+    }
+
+    if let Some(hull) = params.get("hull") {
+        args.hull = hull;
+    }
+
+    args.spectate = params.has("spectate");
+    args.wind_driven_waves = params.has("wind_waves");
+
+    // Picking a room means playing online
+    if let Some(room) = params.get("room") {
+        args.room = room;
+        args.sync_test = false;
+    }
+}
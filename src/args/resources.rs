@@ -1,11 +1,14 @@
 use bevy::prelude::*;
 
-use clap::Parser;
+use clap::{ArgAction, Parser};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
 
-#[derive(Parser, Resource, Debug, Clone)]
+#[derive(Parser, Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct Args {
-    // runs the game in sync test mode / local player mode
-    #[clap(long, default_value = "true")]
+    // runs the game in sync test mode / local player mode, pass --sync-test false to play online
+    #[clap(long, action = ArgAction::Set, default_value_t = true)]
     pub sync_test: bool,
 
     // Set to 2 for doing sync tests, 0 for optimized single player game
@@ -14,4 +17,159 @@ pub struct Args {
 
     #[clap(long, default_value = "1")]
     pub num_players: usize,
+
+    // Name of the ship class in the shipyard all ships are built as
+    #[clap(long, default_value = "Medium")]
+    #[serde(default = "default_hull")]
+    pub hull: String,
+
+    // Derives the waves from the wind instead of using fixed waves. Needs to be the same for all
+    // peers.
+    #[clap(long)]
+    #[serde(default)]
+    pub wind_driven_waves: bool,
+
+    // Signaling server that peers use to find each other when not in sync test mode
+    #[clap(long, default_value = "ws://192.168.100.158:3536")]
+    pub matchbox_server_url: String,
+
+    // Peers joining the same room on the signaling server play together
+    #[clap(long, default_value = "rogue_waves")]
+    pub room: String,
+
+    // Joins the room to watch the players instead of playing
+    #[clap(long)]
+    pub spectate: bool,
+
+    // Number of spectators joining the room, needs to be the same for players and spectators
+    #[clap(long, default_value = "0")]
+    pub num_spectators: usize,
+
+    // Runs the simulation without window or renderer for given number of frames, printing the
+    // resulting ship states
+    #[clap(long)]
+    pub headless_frames: Option<usize>,
+
+    // Records per entity, per component hashes of recent frames and writes the frame to a JSON
+    // file when a desync is detected. Only available in debug builds.
+    #[clap(long)]
+    pub desync_report: bool,
+
+    // Compares two desync reports written by different peers and prints the first component that
+    // differs
+    #[clap(long, num_args = 2, value_names = ["LOCAL_REPORT", "REMOTE_REPORT"])]
+    pub diff_desync_reports: Option<Vec<PathBuf>>,
+
+    // Records the inputs of all players into given replay file when the game exits
+    #[clap(long)]
+    pub record_replay: Option<PathBuf>,
+
+    // Plays back given replay file in a local session instead of reading inputs. Combine with
+    // headless frames to print the resulting ship states without a window.
+    #[clap(long)]
+    pub replay: Option<PathBuf>,
+}
+
+// Replays recorded before ships were defined in the shipyard sailed the medium ship
+fn default_hull() -> String {
+    "Medium".to_string()
+}
+
+#[derive(Debug)]
+pub enum RoomUrlError {
+    InvalidServerUrl(String),
+    InvalidRoom(String),
+    TooFewPlayers(usize),
+    NoSpectatorSeats,
+}
+
+impl fmt::Display for RoomUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoomUrlError::InvalidServerUrl(url) => write!(
+                f,
+                "Invalid matchbox server url \"{url}\", expected ws:// or wss:// followed by a host"
+            ),
+            RoomUrlError::InvalidRoom(room) => write!(
+                f,
+                "Invalid room \"{room}\", expected letters, digits, - or _"
+            ),
+            RoomUrlError::TooFewPlayers(num_players) => {
+                write!(f, "Online play needs at least 2 players, got {num_players}")
+            }
+            RoomUrlError::NoSpectatorSeats => {
+                write!(f, "Spectating needs num spectators to be at least 1")
+            }
+        }
+    }
+}
+
+impl Args {
+    // Url of the room on the signaling server, which starts the session when all players joined
+    pub fn room_url(&self) -> Result<String, RoomUrlError> {
+        let server_url = self.matchbox_server_url.trim_end_matches('/');
+        let host = server_url
+            .strip_prefix("wss://")
+            .or_else(|| server_url.strip_prefix("ws://"))
+            .unwrap_or_default();
+
+        if host.is_empty() || host.contains(['/', '?', '#', ' ']) {
+            return Err(RoomUrlError::InvalidServerUrl(
+                self.matchbox_server_url.clone(),
+            ));
+        }
+
+        if self.room.is_empty()
+            || !self
+                .room
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+        {
+            return Err(RoomUrlError::InvalidRoom(self.room.clone()));
+        }
+
+        if self.num_players < 2 {
+            return Err(RoomUrlError::TooFewPlayers(self.num_players));
+        }
+
+        if self.spectate && self.num_spectators == 0 {
+            return Err(RoomUrlError::NoSpectatorSeats);
+        }
+
+        Ok(format!(
+            "{server_url}/{}?next={}",
+            self.room,
+            self.num_players + self.num_spectators
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn online_mode_is_reachable_from_the_command_line() {
+        let args = Args::parse_from([
+            "pirate-sea-jam",
+            "--sync-test",
+            "false",
+            "--room",
+            "my_room",
+            "--num-players",
+            "2",
+            "--matchbox-server-url",
+            "wss://example.com",
+        ]);
+
+        assert!(!args.sync_test);
+        assert_eq!(args.room_url().unwrap(), "wss://example.com/my_room?next=2");
+    }
+
+    #[test]
+    fn sync_test_is_the_default() {
+        let args = Args::parse_from(["pirate-sea-jam"]);
+
+        assert!(args.sync_test);
+    }
 }
@@ -8,3 +8,23 @@ pub fn sync_test_mode(args: Res<Args>) -> bool {
 pub fn p2p_mode(args: Res<Args>) -> bool {
     !args.sync_test
 }
+
+pub fn desync_report_mode(args: Res<Args>) -> bool {
+    args.desync_report
+}
+
+pub fn replay_mode(args: Res<Args>) -> bool {
+    args.replay.is_some()
+}
+
+pub fn record_replay_mode(args: Res<Args>) -> bool {
+    args.record_replay.is_some()
+}
+
+pub fn spectate_mode(args: Res<Args>) -> bool {
+    args.spectate
+}
+
+pub fn wind_driven_waves_mode(args: Res<Args>) -> bool {
+    args.wind_driven_waves
+}
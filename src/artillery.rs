@@ -1,20 +1,73 @@
-use crate::artillery::components::{ArtilleryAiming, ArtilleryReady};
+use crate::artillery::components::{
+    checksum_artillery, Artillery, ArtilleryAiming, ArtilleryReady, Projectile,
+};
+use crate::artillery::events::{ProjectileHit, ProjectileSplash};
+use crate::artillery::resources::SpawnedSplashes;
 use crate::artillery::systems::{
-    despawn_projectile, register_start_aim_artillery_animations,
-    register_stop_aim_artillery_animations, start_aim_artillery, stop_aim_and_fire_artillery,
+    aim_artillery, animate_artillery_aim, animate_splashes, despawn_hit_projectiles,
+    despawn_reload_indicator, despawn_splashes, draw_aim_prediction, hit_water,
+    register_splash_assets, register_start_aim_artillery_animations,
+    register_stop_aim_artillery_animations, reload_artillery, spawn_reload_indicator,
+    spawn_splashes, start_aim_artillery, stop_aim_and_fire_artillery, tilt_artillery,
+    update_reload_indicator,
 };
-use crate::physics::systems::update_orientation;
+use crate::connection::FPS;
+use crate::desync_report::ChecksumReportApp;
+use crate::game_state::states::GameState;
+use crate::physics::systems::{resolve_collisions, update_linear_velocity, update_orientation};
+use bevy::animation::animation_player;
 use bevy::prelude::*;
+use bevy::transform::TransformSystem;
 use bevy_ggrs::{GgrsApp, GgrsSchedule};
+use std::f32::consts::PI;
 
 pub mod components;
+pub mod events;
 mod resources;
-mod systems;
+pub mod systems;
+
+// Cannons are named by broadside, since the aim animations of all cannons on the same side match
+pub const PORT_CANNON_TAG: &str = "Port cannon";
+pub const STARBOARD_CANNON_TAG: &str = "Starboard cannon";
+
+pub const PROJECTILE_RADIUS: f32 = 0.1;
+
+// Frames a projectile stays in the match, however long it keeps skipping
+pub const PROJECTILE_FRAME_BUDGET: u32 = 6 * FPS as u32;
+
+// Drag of projectiles through the air moving past them, which makes them drift with the wind
+pub const PROJECTILE_DRAG_COEFFICIENT: f32 = 0.002;
+
+// Projectiles hitting the water at a shallower angle than this and faster than the minimum skip
+// speed bounce off, up to the maximum number of skips
+pub const MAX_SKIP_ANGLE: f32 = PI / 18.;
+pub const MIN_SKIP_SPEED: f32 = 8.;
+pub const MAX_PROJECTILE_SKIPS: u32 = 3;
+
+// Share of the vertical speed a skipping projectile bounces back with, and of the horizontal speed
+// it keeps
+pub const SKIP_RESTITUTION: f32 = 0.5;
+pub const SKIP_SPEED_RETENTION: f32 = 0.7;
+
+// Bisection steps locating where the path of a projectile crossed the wave surface
+pub const WATER_ENTRY_ITERATIONS: usize = 8;
+
+pub const SPLASH_SECONDS: f32 = 0.8;
+
+// Frames a cannon takes to reload after firing
+pub const CANNON_RELOAD_FRAMES: u32 = 4 * FPS as u32;
+
+// Distance within which cannons turn to the nearest enemy on their own, about as far as a ball
+// fired at muzzle velocity carries
+pub const TARGETING_RANGE: f32 = 36.;
+
+// Elevations cannons can be tilted to, and the one they fire at without an enemy in range
+pub const MIN_CANNON_ELEVATION: f32 = -PI / 36.;
+pub const MAX_CANNON_ELEVATION: f32 = PI / 4.;
+pub const DEFAULT_CANNON_ELEVATION: f32 = PI / 18.;
 
-pub const PORT_BACK_CANNON_TAG: &str = "Port back cannon";
-pub const PORT_FRONT_CANNON_TAG: &str = "Port front cannon";
-pub const STARBOARD_BACK_CANNON_TAG: &str = "Starboard back cannon";
-pub const STARBOARD_FRONT_CANNON_TAG: &str = "Starboard front cannon";
+// Characters of the reload progress bar shown for each broadside
+pub const RELOAD_BAR_LENGTH: usize = 10;
 
 pub struct ArtilleryPlugin;
 
@@ -23,22 +76,91 @@ impl Plugin for ArtilleryPlugin {
         // Component candidates for roll back
         app.rollback_component_with_copy::<ArtilleryReady>();
         app.rollback_component_with_copy::<ArtilleryAiming>();
+        app.rollback_component_with_copy::<Artillery>();
+        app.rollback_component_with_copy::<Projectile>();
+
+        app.checksum_and_report_component_with_hash::<ArtilleryReady>();
+        app.checksum_and_report_component_with_hash::<ArtilleryAiming>();
+        app.checksum_and_report_component_with_hash::<Projectile>();
+
+        app.add_event::<ProjectileHit>();
+        app.add_event::<ProjectileSplash>();
+        app.checksum_and_report_component::<Artillery>(checksum_artillery);
+
+        app.add_systems(
+            GgrsSchedule,
+            (
+                // Skipping moves the projectile after the physics are done with it
+                hit_water.after(update_orientation),
+                reload_artillery.after(hit_water),
+                start_aim_artillery.after(reload_artillery),
+                aim_artillery.after(start_aim_artillery),
+                stop_aim_and_fire_artillery.after(aim_artillery),
+                // Reads the velocity the projectile hit with, before the impulse bounces it back
+                despawn_hit_projectiles
+                    .after(resolve_collisions)
+                    .before(update_linear_velocity),
+            ),
+        );
+    }
+}
+
+// Cannon tilt and recoil animations and splashes, not needed when running the simulation headless
+pub struct ArtilleryAnimationPlugin;
+
+impl Plugin for ArtilleryAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpawnedSplashes>();
 
         app.add_systems(
             Startup,
             (
                 register_start_aim_artillery_animations,
                 register_stop_aim_artillery_animations,
+                register_splash_assets,
             ),
         );
 
         app.add_systems(
-            GgrsSchedule,
-            (
-                start_aim_artillery.after(update_orientation),
-                stop_aim_and_fire_artillery.after(start_aim_artillery),
-                despawn_projectile,
-            ),
+            Update,
+            (animate_artillery_aim, spawn_splashes, animate_splashes)
+                .run_if(in_state(GameState::InGame)),
+        );
+        app.add_systems(OnExit(GameState::InGame), despawn_splashes);
+
+        app.add_systems(
+            PostUpdate,
+            tilt_artillery
+                .after(animation_player)
+                .before(TransformSystem::TransformPropagate)
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
+
+// Predicted trajectories of the aiming cannons of local ships, so that players learn the
+// ballistics
+pub struct AimPredictionPlugin;
+
+impl Plugin for AimPredictionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            draw_aim_prediction.run_if(in_state(GameState::InGame)),
+        );
+    }
+}
+
+// Reload progress of the broadsides of local ships
+pub struct ReloadIndicatorPlugin;
+
+impl Plugin for ReloadIndicatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InGame), spawn_reload_indicator);
+        app.add_systems(
+            Update,
+            update_reload_indicator.run_if(in_state(GameState::InGame)),
         );
+        app.add_systems(OnExit(GameState::InGame), despawn_reload_indicator);
     }
 }
@@ -1,16 +1,93 @@
+use crate::inputs::Broadside;
+use crate::utils::hash::hash_f32_number;
 use bevy::prelude::*;
+use bevy::utils::FixedState;
+use std::hash::{BuildHasher, Hash, Hasher};
 
-#[derive(Component, Clone, Copy, Default)]
-pub struct ArtilleryReady(pub bool);
+// Whether every cannon of each broadside of a ship is loaded
+#[derive(Component, Clone, Copy, Default, Hash)]
+pub struct ArtilleryReady {
+    pub port: bool,
+    pub starboard: bool,
+}
 
-#[derive(Component, Clone, Copy, Default)]
+impl ArtilleryReady {
+    pub fn is_ready(&self, broadside: Broadside) -> bool {
+        match broadside {
+            Broadside::Port => self.port,
+            Broadside::Starboard => self.starboard,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Default, Hash)]
 pub struct ArtilleryAiming(pub bool);
 
-#[derive(Component, Clone, Copy, Default)]
-pub struct Projectile;
+// Tells projectiles apart the same way every time the frame they were fired on is simulated
+// again, unlike their entities, which change when a rollback spawns them anew
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct ProjectileId {
+    pub shooter: usize,
+    pub fired_frame: u32,
+    // Order in which the cannons of the ship fired
+    pub cannon: usize,
+}
+
+#[derive(Component, Clone, Copy, Default, Hash)]
+pub struct Projectile {
+    // Handle of the player whose ship fired the projectile
+    pub shooter: usize,
+    pub fired_frame: u32,
+    pub cannon: usize,
+    // Frames left until the projectile is removed, however long it keeps skipping
+    pub frames_left: u32,
+    // Times the projectile skipped off the water
+    pub skips: u32,
+}
+
+impl Projectile {
+    pub fn id(&self) -> ProjectileId {
+        ProjectileId {
+            shooter: self.shooter,
+            fired_frame: self.fired_frame,
+            cannon: self.cannon,
+        }
+    }
+}
 
 #[derive(Component, Clone, Copy, Default)]
 pub struct Artillery {
     pub muzzle_velocity: f32,
+    pub recoil_impulse: f32,
     pub is_aiming: bool,
+    pub broadside: Broadside,
+    // Frames left until the cannon can fire again, counted in the rollback schedule so that every
+    // peer reloads on the same frame
+    pub reload_frames: u32,
+    // Angle above the horizon the cannon fires at, solved while aiming
+    pub elevation: f32,
+}
+
+pub fn checksum_artillery(value: &Artillery) -> u64 {
+    let mut hasher = FixedState.build_hasher();
+    hash_f32_number(value.muzzle_velocity).hash(&mut hasher);
+    hash_f32_number(value.recoil_impulse).hash(&mut hasher);
+    value.is_aiming.hash(&mut hasher);
+    value.broadside.hash(&mut hasher);
+    value.reload_frames.hash(&mut hasher);
+    hash_f32_number(value.elevation).hash(&mut hasher);
+
+    #[allow(clippy::manual_hash_one)]
+    hasher.finish()
+}
+
+// Shows how far along the reload of each broadside of the local ships is
+#[derive(Component)]
+pub struct ReloadIndicator;
+
+// Water thrown up where a projectile hit the sea, shrinking away once its timer runs out
+#[derive(Component)]
+pub struct Splash {
+    pub timer: Timer,
+    pub size: f32,
 }
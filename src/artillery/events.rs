@@ -0,0 +1,20 @@
+use crate::artillery::components::ProjectileId;
+use bevy::prelude::*;
+
+// Sent from the rollback schedule when a projectile hits a ship of another player
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ProjectileHit {
+    pub shooter: usize,
+    pub target: usize,
+    pub point: Vec3,
+    pub velocity: Vec3,
+}
+
+// Sent from the rollback schedule where a projectile hits the water, whether it skips or sinks
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ProjectileSplash {
+    pub projectile: ProjectileId,
+    pub frame: u32,
+    pub point: Vec3,
+    pub velocity: Vec3,
+}
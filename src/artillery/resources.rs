@@ -1,5 +1,6 @@
+use crate::artillery::components::ProjectileId;
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Resource)]
 pub struct StartAimArtilleryAnimationClips {
@@ -10,3 +11,14 @@ pub struct StartAimArtilleryAnimationClips {
 pub struct EndAimArtilleryAnimationClips {
     pub handles: HashMap<&'static str, Handle<AnimationClip>>,
 }
+
+#[derive(Resource)]
+pub struct SplashAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+// Splashes spawned for the frames that may still be simulated again after a rollback, which sends
+// their splash events once more
+#[derive(Resource, Default)]
+pub struct SpawnedSplashes(pub HashSet<(ProjectileId, u32)>);
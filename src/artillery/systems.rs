@@ -1,92 +1,289 @@
-use crate::artillery::components::{Artillery, ArtilleryAiming, Projectile};
-use crate::artillery::resources::{EndAimArtilleryAnimationClips, StartAimArtilleryAnimationClips};
+use crate::artillery::components::{
+    Artillery, ArtilleryAiming, ArtilleryReady, Projectile, ReloadIndicator, Splash,
+};
+use crate::artillery::events::{ProjectileHit, ProjectileSplash};
+use crate::artillery::resources::{
+    EndAimArtilleryAnimationClips, SpawnedSplashes, SplashAssets, StartAimArtilleryAnimationClips,
+};
 use crate::artillery::{
-    PORT_BACK_CANNON_TAG, PORT_FRONT_CANNON_TAG, STARBOARD_BACK_CANNON_TAG,
-    STARBOARD_FRONT_CANNON_TAG,
+    CANNON_RELOAD_FRAMES, DEFAULT_CANNON_ELEVATION, MAX_CANNON_ELEVATION, MAX_PROJECTILE_SKIPS,
+    MAX_SKIP_ANGLE, MIN_CANNON_ELEVATION, MIN_SKIP_SPEED, PORT_CANNON_TAG,
+    PROJECTILE_DRAG_COEFFICIENT, PROJECTILE_FRAME_BUDGET, PROJECTILE_RADIUS, RELOAD_BAR_LENGTH,
+    SKIP_RESTITUTION, SKIP_SPEED_RETENTION, SPLASH_SECONDS, STARBOARD_CANNON_TAG, TARGETING_RANGE,
+    WATER_ENTRY_ITERATIONS,
 };
 use crate::assets::resources::ModelAssets;
+use crate::camera::resources::MainCamera;
 use crate::connection::systems::RollbackConfig;
-use crate::inputs::fire;
+use crate::connection::MAX_PREDICTION;
+use crate::damage::components::Sinking;
+use crate::inputs::{broadside, fire, Broadside};
 use crate::ocean::resources::Wave;
 use crate::physics::bundles::ParticleBundle;
-use crate::physics::components::{ExternalImpulse, ExternalTorqueImpulse, LinearVelocity};
+use crate::physics::components::{
+    Collider, ColliderShape, CollisionGroup, ExternalImpulse, ExternalTorqueImpulse, LinearDamping,
+    LinearDrag, LinearVelocity, Mass, WindDrift,
+};
+use crate::physics::events::Collision;
+use crate::physics::resources::Gravity;
 use crate::player::components::Player;
-use crate::utils::linear_algebra::is_facing;
+use crate::simulation::resources::SimulationClock;
+use crate::utils::ballistics::{lead_target, Flight};
+use crate::wind::resources::Wind;
 use bevy::animation::RepeatAnimation;
+use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
-use bevy_ggrs::{AddRollbackCommandExtension, PlayerInputs};
+use bevy_ggrs::{AddRollbackCommandExtension, LocalPlayers, PlayerInputs};
 use std::collections::HashMap;
 use std::f32::consts::PI;
 use std::time::Duration;
 
+// Counts down the reload of every cannon, and tells which broadsides are ready to fire again
+pub fn reload_artillery(
+    mut artillery_query: Query<&mut Artillery>,
+    children_query: Query<&Children>,
+    mut player_query: Query<(Entity, &mut ArtilleryReady)>,
+) {
+    for (ship_entity, mut artillery_ready) in &mut player_query {
+        let mut ready = ArtilleryReady {
+            port: true,
+            starboard: true,
+        };
+
+        for descendant in children_query.iter_descendants(ship_entity) {
+            let Ok(mut artillery) = artillery_query.get_mut(descendant) else {
+                continue;
+            };
+
+            artillery.reload_frames = artillery.reload_frames.saturating_sub(1);
+            if artillery.reload_frames > 0 {
+                match artillery.broadside {
+                    Broadside::Port => ready.port = false,
+                    Broadside::Starboard => ready.starboard = false,
+                }
+            }
+        }
+
+        *artillery_ready = ready;
+    }
+}
+
+// Closest ship of another player within range and its velocity, ties going to the lowest handle
+// so that every peer picks the same one
+fn nearest_target<'a>(
+    translation: Vec3,
+    handle: usize,
+    targets: impl Iterator<Item = (&'a GlobalTransform, &'a LinearVelocity, &'a Player)>,
+) -> Option<(Vec3, Vec3)> {
+    targets
+        .filter(|(_, _, player)| player.handle != handle)
+        .map(|(global_transform, linear_velocity, player)| {
+            let target_translation = global_transform.translation();
+            (
+                translation.distance_squared(target_translation),
+                player.handle,
+                target_translation,
+                linear_velocity.0,
+            )
+        })
+        .filter(|(distance_squared, _, _, _)| *distance_squared <= TARGETING_RANGE.powi(2))
+        .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+        .map(|(_, _, target_translation, target_velocity)| (target_translation, target_velocity))
+}
+
+// Horizontal direction out of the broadside of a cannon, the only one it can fire in
+fn firing_axis(artillery_global_transform: &GlobalTransform) -> Vec3 {
+    (artillery_global_transform.left() * Vec3::new(1., 0., 1.)).normalize_or_zero()
+}
+
+// Cannons can't traverse, so they fire straight out of their broadside, tilted to their elevation
+fn firing_direction(artillery_global_transform: &GlobalTransform, elevation: f32) -> Vec3 {
+    firing_axis(artillery_global_transform) * elevation.cos() + Vec3::Y * elevation.sin()
+}
+
+// Flight of the balls fired by cannons, as the physics step it with the drag and wind drift they
+// are spawned with
+fn projectile_flight(gravity: &Gravity, wind: &Wind, clock: &SimulationClock) -> Flight {
+    Flight {
+        gravity: gravity.0,
+        wind: wind.0,
+        drag_per_mass: PROJECTILE_DRAG_COEFFICIENT / Mass::default().0,
+        damping: LinearDamping::default().0,
+        delta_seconds: clock.delta_seconds,
+        max_frames: PROJECTILE_FRAME_BUDGET as usize,
+    }
+}
+
+// Elevation that brings the ball down abeam of the target, or carries it as far as possible
+// towards it if it is out of reach. None while the target is not off the broadside of the cannon.
+fn target_elevation(
+    artillery_global_transform: &GlobalTransform,
+    shooter_velocity: Vec3,
+    (target_translation, target_velocity): (Vec3, Vec3),
+    muzzle_velocity: f32,
+    flight: &Flight,
+) -> Option<f32> {
+    let origin = artillery_global_transform.translation();
+    let axis = firing_axis(artillery_global_transform);
+    if (target_translation - origin).dot(axis) <= 0. {
+        return None;
+    }
+
+    let elevation = lead_target(
+        origin,
+        shooter_velocity,
+        axis,
+        target_translation,
+        target_velocity,
+        muzzle_velocity,
+        flight,
+    )
+    .map_or(MAX_CANNON_ELEVATION, |lead| {
+        lead.elevation
+            .clamp(MIN_CANNON_ELEVATION, MAX_CANNON_ELEVATION)
+    });
+
+    Some(elevation)
+}
+
 pub fn start_aim_artillery(
-    mut artillery_query: Query<(
-        &GlobalTransform,
-        &Name,
-        &mut Artillery,
-        &mut AnimationPlayer,
-    )>,
+    mut artillery_query: Query<&mut Artillery>,
     children_query: Query<&Children>,
-    mut player_query: Query<(Entity, &GlobalTransform, &mut ArtilleryAiming, &Player)>,
+    mut player_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &mut ArtilleryAiming,
+            &ArtilleryReady,
+            &Player,
+        ),
+        Without<Sinking>,
+    >,
+    target_query: Query<(&GlobalTransform, &LinearVelocity, &Player), Without<Sinking>>,
     inputs: Res<PlayerInputs<RollbackConfig>>,
-    animation_clips: Res<StartAimArtilleryAnimationClips>,
 ) {
-    let dummy_closest_target = Vec3::ZERO;
-
-    for (ship_entity, vessel_global_transform, mut artillery_aiming, player) in &mut player_query {
+    for (ship_entity, vessel_global_transform, mut artillery_aiming, artillery_ready, player) in
+        &mut player_query
+    {
         let (input, _) = inputs[player.handle];
-        if fire(input) && !artillery_aiming.0 {
-            artillery_aiming.0 = true;
+        if !fire(input) || artillery_aiming.0 {
+            continue;
+        }
 
-            for descendant in children_query.iter_descendants(ship_entity) {
-                if let Ok((global_transform, name, mut artillery, mut animation_player)) =
-                    artillery_query.get_mut(descendant)
-                {
-                    if is_facing(
-                        global_transform.left(),
-                        vessel_global_transform.translation(),
-                        dummy_closest_target,
-                    ) {
-                        if let Some(animation_clip_handle) =
-                            animation_clips.handles.get(name.as_str())
-                        {
-                            artillery.is_aiming = true;
-
-                            animation_player
-                                .play_with_transition(
-                                    animation_clip_handle.clone_weak(),
-                                    Duration::from_secs(0.6 as u64),
-                                )
-                                .repeat();
-                        }
-                    }
+        // The bow points along the local back direction of the ship, which puts port on its local
+        // right. Without an enemy in range, the player picks the side.
+        let translation = vessel_global_transform.translation();
+        let broadside = match nearest_target(translation, player.handle, target_query.iter()) {
+            Some((target_translation, _))
+                if (target_translation - translation).dot(vessel_global_transform.right()) < 0. =>
+            {
+                Broadside::Starboard
+            }
+            Some(_) => Broadside::Port,
+            None => broadside(input),
+        };
+
+        // Holding fire while the broadside reloads starts aiming as soon as it is loaded
+        if !artillery_ready.is_ready(broadside) {
+            continue;
+        }
+
+        artillery_aiming.0 = true;
+
+        for descendant in children_query.iter_descendants(ship_entity) {
+            if let Ok(mut artillery) = artillery_query.get_mut(descendant) {
+                if artillery.broadside == broadside {
+                    artillery.is_aiming = true;
                 }
             }
         }
     }
 }
 
+// Keeps the aiming cannons of each ship elevated to hit the nearest enemy in range, leading it by
+// how the ships move while the ball is in flight
+pub fn aim_artillery(
+    mut artillery_query: Query<(&GlobalTransform, &mut Artillery)>,
+    children_query: Query<&Children>,
+    player_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &LinearVelocity,
+            &ArtilleryAiming,
+            &Player,
+        ),
+        Without<Sinking>,
+    >,
+    target_query: Query<(&GlobalTransform, &LinearVelocity, &Player), Without<Sinking>>,
+    gravity: Res<Gravity>,
+    wind: Res<Wind>,
+    clock: Res<SimulationClock>,
+) {
+    let flight = projectile_flight(&gravity, &wind, &clock);
+
+    for (ship_entity, vessel_global_transform, linear_velocity, artillery_aiming, player) in
+        &player_query
+    {
+        if !artillery_aiming.0 {
+            continue;
+        }
+
+        let target = nearest_target(
+            vessel_global_transform.translation(),
+            player.handle,
+            target_query.iter(),
+        );
+
+        for descendant in children_query.iter_descendants(ship_entity) {
+            let Ok((artillery_global_transform, mut artillery)) =
+                artillery_query.get_mut(descendant)
+            else {
+                continue;
+            };
+
+            if !artillery.is_aiming {
+                continue;
+            }
+
+            // A nearest enemy off the other broadside leaves the cannon where it was aimed
+            artillery.elevation = match target {
+                Some(target) => target_elevation(
+                    artillery_global_transform,
+                    linear_velocity.0,
+                    target,
+                    artillery.muzzle_velocity,
+                    &flight,
+                )
+                .unwrap_or(artillery.elevation),
+                None => DEFAULT_CANNON_ELEVATION,
+            };
+        }
+    }
+}
+
 pub fn stop_aim_and_fire_artillery(
     mut commands: Commands,
     model_assets: Res<ModelAssets>,
     inputs: Res<PlayerInputs<RollbackConfig>>,
-    mut player_query: Query<(
-        &mut ArtilleryAiming,
-        &Player,
-        &LinearVelocity,
-        &mut ExternalImpulse,
-        &mut ExternalTorqueImpulse,
-        &GlobalTransform,
-    )>,
-    mut artillery_query: Query<(
-        &GlobalTransform,
-        &Name,
-        &mut Artillery,
-        &mut AnimationPlayer,
-    )>,
-    animation_clips: Res<EndAimArtilleryAnimationClips>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut ArtilleryAiming,
+            &Player,
+            &LinearVelocity,
+            &mut ExternalImpulse,
+            &mut ExternalTorqueImpulse,
+            &GlobalTransform,
+        ),
+        Without<Sinking>,
+    >,
+    children_query: Query<&Children>,
+    mut artillery_query: Query<(&GlobalTransform, &mut Artillery)>,
+    clock: Res<SimulationClock>,
 ) {
     for (
+        ship_entity,
         mut artillery_aiming,
         player,
         linear_velocity,
@@ -99,21 +296,17 @@ pub fn stop_aim_and_fire_artillery(
         if !fire(input) && artillery_aiming.0 {
             artillery_aiming.0 = false;
 
-            for (artillery_global_transform, name, mut artillery, mut animation_player) in
-                &mut artillery_query
-            {
+            let mut cannon = 0;
+            for descendant in children_query.iter_descendants(ship_entity) {
+                let Ok((artillery_global_transform, mut artillery)) =
+                    artillery_query.get_mut(descendant)
+                else {
+                    continue;
+                };
+
                 if artillery.is_aiming {
                     artillery.is_aiming = false;
-
-                    if let Some(animation_clip_handle) = animation_clips.handles.get(name.as_str())
-                    {
-                        animation_player
-                            .set_repeat(RepeatAnimation::Never)
-                            .play_with_transition(
-                                animation_clip_handle.clone_weak(),
-                                Duration::from_secs(1.2 as u64),
-                            );
-                    }
+                    artillery.reload_frames = CANNON_RELOAD_FRAMES;
 
                     // Spawn projectile
                     commands
@@ -126,18 +319,42 @@ pub fn stop_aim_and_fire_artillery(
                                 ..default()
                             },
                             Name::new("Projectile"),
-                            Projectile,
+                            Projectile {
+                                shooter: player.handle,
+                                fired_frame: clock.frame,
+                                cannon,
+                                frames_left: PROJECTILE_FRAME_BUDGET,
+                                ..default()
+                            },
+                            Collider {
+                                shape: ColliderShape::Sphere {
+                                    radius: PROJECTILE_RADIUS,
+                                },
+                                ..default()
+                            },
+                            // Leaves the cannon without hitting its own ship
+                            CollisionGroup(player.handle),
                             ParticleBundle {
                                 linear_velocity: LinearVelocity(
-                                    artillery_global_transform.left() * artillery.muzzle_velocity
+                                    firing_direction(
+                                        artillery_global_transform,
+                                        artillery.elevation,
+                                    ) * artillery.muzzle_velocity
                                         + linear_velocity.0,
                                 ),
                                 ..default()
                             },
+                            LinearDrag {
+                                velocity_squared_drag_coefficient: PROJECTILE_DRAG_COEFFICIENT,
+                                ..default()
+                            },
+                            WindDrift,
                         ))
                         .add_rollback();
+                    cannon += 1;
 
-                    let recoil_impulse = artillery_global_transform.right() * 60.;
+                    let recoil_impulse =
+                        artillery_global_transform.right() * artillery.recoil_impulse;
 
                     external_torque_impulse.0 += (artillery_global_transform.translation()
                         - vessel_global_transform.translation())
@@ -149,67 +366,284 @@ pub fn stop_aim_and_fire_artillery(
     }
 }
 
-pub fn despawn_projectile(
+// Follows the aiming state of the rollback simulation, so that the animations are only started
+// and stopped when the state actually flips
+pub fn animate_artillery_aim(
+    mut artillery_query: Query<(&Name, &Artillery, &mut AnimationPlayer), Changed<Artillery>>,
+    start_aim_animation_clips: Res<StartAimArtilleryAnimationClips>,
+    end_aim_animation_clips: Res<EndAimArtilleryAnimationClips>,
+) {
+    for (name, artillery, mut animation_player) in &mut artillery_query {
+        let (Some(start_aim_clip_handle), Some(end_aim_clip_handle)) = (
+            start_aim_animation_clips.handles.get(name.as_str()),
+            end_aim_animation_clips.handles.get(name.as_str()),
+        ) else {
+            continue;
+        };
+
+        let is_playing_aim_animation = animation_player.is_playing_clip(start_aim_clip_handle);
+
+        if artillery.is_aiming && !is_playing_aim_animation {
+            animation_player
+                .play_with_transition(
+                    start_aim_clip_handle.clone_weak(),
+                    Duration::from_secs(0.6 as u64),
+                )
+                .repeat();
+        } else if !artillery.is_aiming && is_playing_aim_animation {
+            animation_player
+                .set_repeat(RepeatAnimation::Never)
+                .play_with_transition(
+                    end_aim_clip_handle.clone_weak(),
+                    Duration::from_secs(1.2 as u64),
+                );
+        }
+    }
+}
+
+// Runs after the animations, which would otherwise reset the rotation of the cannons
+pub fn tilt_artillery(mut artillery_query: Query<(&Artillery, &mut Transform)>) {
+    for (artillery, mut transform) in &mut artillery_query {
+        if !artillery.is_aiming {
+            continue;
+        }
+
+        let rest_rotation = match artillery.broadside {
+            Broadside::Port => Quat::from_rotation_y(PI),
+            Broadside::Starboard => Quat::IDENTITY,
+        };
+        transform.rotation = rest_rotation * Quat::from_rotation_z(-artillery.elevation);
+    }
+}
+
+// Traces the flight of the balls the aiming cannons of local ships would fire, down to the height
+// of the nearest enemy in range or to sea level, and how far across the line of fire the ball
+// passes the enemy by
+pub fn draw_aim_prediction(
+    mut gizmos: Gizmos,
+    player_query: Query<(Entity, &GlobalTransform, &LinearVelocity, &Player)>,
+    target_query: Query<(&GlobalTransform, &LinearVelocity, &Player), Without<Sinking>>,
+    children_query: Query<&Children>,
+    artillery_query: Query<(&GlobalTransform, &Artillery)>,
+    gravity: Res<Gravity>,
+    wind: Res<Wind>,
+    clock: Res<SimulationClock>,
+    local_players: Res<LocalPlayers>,
+) {
+    let flight = projectile_flight(&gravity, &wind, &clock);
+
+    for (ship_entity, vessel_global_transform, linear_velocity, player) in &player_query {
+        if !local_players.0.contains(&player.handle) {
+            continue;
+        }
+
+        let target = nearest_target(
+            vessel_global_transform.translation(),
+            player.handle,
+            target_query.iter(),
+        );
+        let impact_height = target.map_or(0., |(target_translation, _)| target_translation.y);
+
+        for descendant in children_query.iter_descendants(ship_entity) {
+            let Ok((artillery_global_transform, artillery)) = artillery_query.get(descendant)
+            else {
+                continue;
+            };
+
+            if !artillery.is_aiming {
+                continue;
+            }
+
+            let origin = artillery_global_transform.translation();
+            let velocity = firing_direction(artillery_global_transform, artillery.elevation)
+                * artillery.muzzle_velocity
+                + linear_velocity.0;
+            let Some(descent) = flight.descend_to(origin, velocity, impact_height) else {
+                continue;
+            };
+
+            let frames = (descent.time / flight.delta_seconds) as usize + 1;
+            gizmos.linestrip(
+                flight
+                    .path(origin, velocity)
+                    .take(frames)
+                    .chain([descent.impact]),
+                Color::WHITE,
+            );
+            gizmos.circle(descent.impact, Direction3d::Y, 1., Color::ORANGE_RED);
+
+            let Some((target_translation, target_velocity)) = target else {
+                continue;
+            };
+            let axis = firing_axis(artillery_global_transform);
+            if (target_translation - origin).dot(axis) <= 0. {
+                continue;
+            }
+            if let Some(lead) = lead_target(
+                origin,
+                linear_velocity.0,
+                axis,
+                target_translation,
+                target_velocity,
+                artillery.muzzle_velocity,
+                &flight,
+            ) {
+                gizmos.line(
+                    descent.impact,
+                    descent.impact + lead.miss,
+                    Color::ORANGE_RED,
+                );
+            }
+        }
+    }
+}
+
+// Point where the path of a projectile over the last frame went below the wave surface
+fn water_entry_point(from: Vec3, to: Vec3, wave: &Wave) -> Vec3 {
+    let depth = |point: Vec3| wave.height(point, wave.configs, wave.phases) - point.y;
+
+    // A fixed number of steps, so that every peer arrives at the same point
+    let (mut above, mut below) = (from, to);
+    for _ in 0..WATER_ENTRY_ITERATIONS {
+        let middle = above.lerp(below, 0.5);
+        if depth(middle) > 0. {
+            below = middle;
+        } else {
+            above = middle;
+        }
+    }
+
+    above.lerp(below, 0.5)
+}
+
+// Velocity a projectile hitting the water bounces off with, if it comes in flat and fast enough and
+// has skips left
+fn skip_velocity(velocity: Vec3, skips: u32) -> Option<Vec3> {
+    let entry_angle = (-velocity.y).atan2(velocity.xz().length());
+    let can_skip = entry_angle <= MAX_SKIP_ANGLE
+        && velocity.length() >= MIN_SKIP_SPEED
+        && skips < MAX_PROJECTILE_SKIPS;
+
+    can_skip.then(|| {
+        Vec3::new(
+            velocity.x * SKIP_SPEED_RETENTION,
+            -velocity.y * SKIP_RESTITUTION,
+            velocity.z * SKIP_SPEED_RETENTION,
+        )
+    })
+}
+
+// Projectiles coming in flat and fast enough skip off the water, others splash in and are removed.
+// Projectiles that keep flying are removed once their frame budget runs out.
+pub fn hit_water(
     mut commands: Commands,
-    projectile_query: Query<(Entity, &GlobalTransform), With<Projectile>>,
+    mut projectile_query: Query<(Entity, &mut Projectile, &mut Transform, &mut LinearVelocity)>,
+    mut projectile_splash_event_writer: EventWriter<ProjectileSplash>,
     wave: Res<Wave>,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
 ) {
-    let elapsed_time = time.elapsed().as_secs_f32();
-    for (entity, global_transform) in &projectile_query {
-        let translation = global_transform.translation();
-        let wave_height = wave.height(translation, wave.configs, elapsed_time);
-        if translation.y + 2. < wave_height {
+    for (entity, mut projectile, mut transform, mut linear_velocity) in &mut projectile_query {
+        projectile.frames_left = projectile.frames_left.saturating_sub(1);
+        if projectile.frames_left == 0 {
             commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let translation = transform.translation;
+        if translation.y > wave.height(translation, wave.configs, wave.phases) {
+            continue;
+        }
+
+        let velocity = linear_velocity.0;
+        let entry_point = water_entry_point(
+            translation - velocity * clock.delta_seconds,
+            translation,
+            &wave,
+        );
+
+        projectile_splash_event_writer.send(ProjectileSplash {
+            projectile: projectile.id(),
+            frame: clock.frame,
+            point: entry_point,
+            velocity,
+        });
+
+        match skip_velocity(velocity, projectile.skips) {
+            Some(skip_velocity) => {
+                projectile.skips += 1;
+                transform.translation = entry_point;
+                linear_velocity.0 = skip_velocity;
+            }
+            None => {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+// Projectiles stop at the first ship they hit
+pub fn despawn_hit_projectiles(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<Collision>,
+    mut projectile_hit_event_writer: EventWriter<ProjectileHit>,
+    projectile_query: Query<(&Projectile, &LinearVelocity)>,
+    player_query: Query<&Player>,
+) {
+    let mut hit_projectiles = Vec::new();
+    for collision in collision_event_reader.read() {
+        for (projectile_entity, target_entity) in [
+            (collision.entity_a, collision.entity_b),
+            (collision.entity_b, collision.entity_a),
+        ] {
+            let (Ok((projectile, linear_velocity)), Ok(target)) = (
+                projectile_query.get(projectile_entity),
+                player_query.get(target_entity),
+            ) else {
+                continue;
+            };
+
+            if hit_projectiles.contains(&projectile_entity) {
+                continue;
+            }
+            hit_projectiles.push(projectile_entity);
+
+            projectile_hit_event_writer.send(ProjectileHit {
+                shooter: projectile.shooter,
+                target: target.handle,
+                point: collision.point,
+                velocity: linear_velocity.0,
+            });
+
+            commands.entity(projectile_entity).despawn_recursive();
         }
     }
 }
 
+// The aim clips only hold the cannons level, the tilt to the elevation of the firing solution is
+// applied on top of them by `tilt_artillery`
 pub fn register_start_aim_artillery_animations(
     mut commands: Commands,
     mut animations: ResMut<Assets<AnimationClip>>,
 ) {
-    let keyframe_timestamps = vec![0.0, 0.6, 1.2];
+    let keyframe_timestamps = vec![0.0, 0.6];
 
     let mut animation_clip_handles = HashMap::new();
 
     let tilt_port_cannon_animation_curve = VariableCurve {
         keyframe_timestamps: keyframe_timestamps.clone(),
-        keyframes: Keyframes::Rotation(vec![
-            Quat::from_rotation_y(PI) * Quat::from_rotation_z(0.),
-            Quat::from_rotation_y(PI) * Quat::from_rotation_z(-30_f32.to_radians()),
-            Quat::from_rotation_y(PI) * Quat::from_rotation_z(0.),
-        ]),
+        keyframes: Keyframes::Rotation(vec![Quat::from_rotation_y(PI); 2]),
         interpolation: Interpolation::Linear,
     };
 
     let tilt_starboard_cannon_animation_curve = VariableCurve {
         keyframe_timestamps: keyframe_timestamps.clone(),
-        keyframes: Keyframes::Rotation(vec![
-            Quat::from_rotation_z(0.),
-            Quat::from_rotation_z(-30_f32.to_radians()),
-            Quat::from_rotation_z(0.),
-        ]),
+        keyframes: Keyframes::Rotation(vec![Quat::IDENTITY; 2]),
         interpolation: Interpolation::Linear,
     };
 
     for (tag, tilt_curve) in [
-        (
-            PORT_BACK_CANNON_TAG,
-            tilt_port_cannon_animation_curve.clone(),
-        ),
-        (
-            PORT_FRONT_CANNON_TAG,
-            tilt_port_cannon_animation_curve.clone(),
-        ),
-        (
-            STARBOARD_BACK_CANNON_TAG,
-            tilt_starboard_cannon_animation_curve.clone(),
-        ),
-        (
-            STARBOARD_FRONT_CANNON_TAG,
-            tilt_starboard_cannon_animation_curve.clone(),
-        ),
+        (PORT_CANNON_TAG, tilt_port_cannon_animation_curve),
+        (STARBOARD_CANNON_TAG, tilt_starboard_cannon_animation_curve),
     ] {
         let mut animation_clip = AnimationClip::default();
 
@@ -260,21 +694,10 @@ pub fn register_stop_aim_artillery_animations(
     };
 
     for (tag, reset_tilt_curve) in [
+        (PORT_CANNON_TAG, reset_tilt_port_cannon_animation_curve),
         (
-            PORT_BACK_CANNON_TAG,
-            reset_tilt_port_cannon_animation_curve.clone(),
-        ),
-        (
-            PORT_FRONT_CANNON_TAG,
-            reset_tilt_port_cannon_animation_curve.clone(),
-        ),
-        (
-            STARBOARD_BACK_CANNON_TAG,
-            reset_tilt_starboard_cannon_animation_curve.clone(),
-        ),
-        (
-            STARBOARD_FRONT_CANNON_TAG,
-            reset_tilt_starboard_cannon_animation_curve.clone(),
+            STARBOARD_CANNON_TAG,
+            reset_tilt_starboard_cannon_animation_curve,
         ),
     ] {
         let mut animation_clip = AnimationClip::default();
@@ -302,3 +725,238 @@ pub fn register_stop_aim_artillery_animations(
         handles: animation_clip_handles,
     });
 }
+
+pub fn register_splash_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(SplashAssets {
+        mesh: meshes.add(Cylinder::new(0.3, 1.)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.9, 0.95, 1.),
+            perceptual_roughness: 0.8,
+            ..default()
+        }),
+    });
+}
+
+// Faster projectiles throw up larger splashes. Each splash is spawned once, however often its frame
+// is simulated again.
+pub fn spawn_splashes(
+    mut commands: Commands,
+    mut projectile_splash_event_reader: EventReader<ProjectileSplash>,
+    mut spawned_splashes: ResMut<SpawnedSplashes>,
+    splash_assets: Res<SplashAssets>,
+    clock: Res<SimulationClock>,
+) {
+    // Rollbacks reach no further back than the prediction window
+    spawned_splashes
+        .0
+        .retain(|(_, frame)| frame + MAX_PREDICTION as u32 >= clock.frame);
+
+    for splash in projectile_splash_event_reader.read() {
+        if !spawned_splashes.0.insert((splash.projectile, splash.frame)) {
+            continue;
+        }
+
+        commands.spawn((
+            PbrBundle {
+                mesh: splash_assets.mesh.clone(),
+                material: splash_assets.material.clone(),
+                transform: Transform::from_translation(splash.point).with_scale(Vec3::ZERO),
+                ..default()
+            },
+            Splash {
+                timer: Timer::from_seconds(SPLASH_SECONDS, TimerMode::Once),
+                size: (splash.velocity.length() / 12.).clamp(0.5, 2.),
+            },
+            Name::new("Splash"),
+        ));
+    }
+}
+
+// Splashes shoot up and widen, then collapse back into the sea
+pub fn animate_splashes(
+    mut commands: Commands,
+    mut splash_query: Query<(Entity, &mut Splash, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut splash, mut transform) in &mut splash_query {
+        splash.timer.tick(time.delta());
+        if splash.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let progress = splash.timer.fraction();
+        let width = splash.size * (1. + progress);
+        let height = splash.size * 2. * (progress * PI).sin();
+        transform.scale = Vec3::new(width, height, width);
+    }
+}
+
+pub fn despawn_splashes(
+    mut commands: Commands,
+    splash_query: Query<Entity, With<Splash>>,
+    mut spawned_splashes: ResMut<SpawnedSplashes>,
+) {
+    for entity in &splash_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    spawned_splashes.0.clear();
+}
+
+pub fn spawn_reload_indicator(mut commands: Commands, main_camera: Res<MainCamera>) {
+    commands.spawn((
+        // Seems to be required in dev builds since using editor plugin results in multiple
+        // cameras
+        TargetCamera(main_camera.id),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 18.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(16.),
+            left: Val::Px(16.),
+            ..default()
+        }),
+        ReloadIndicator,
+    ));
+}
+
+fn reload_bar(reload_frames: u32) -> String {
+    if reload_frames == 0 {
+        return "ready".to_string();
+    }
+
+    let filled = RELOAD_BAR_LENGTH
+        - (reload_frames as usize * RELOAD_BAR_LENGTH).div_ceil(CANNON_RELOAD_FRAMES as usize);
+    format!(
+        "[{}{}]",
+        "#".repeat(filled),
+        "-".repeat(RELOAD_BAR_LENGTH - filled)
+    )
+}
+
+pub fn update_reload_indicator(
+    mut indicator_query: Query<&mut Text, With<ReloadIndicator>>,
+    player_query: Query<(Entity, &Player)>,
+    children_query: Query<&Children>,
+    artillery_query: Query<&Artillery>,
+    local_players: Res<LocalPlayers>,
+) {
+    let mut local_ships: Vec<_> = player_query
+        .iter()
+        .filter(|(_, player)| local_players.0.contains(&player.handle))
+        .collect();
+    local_ships.sort_by_key(|(_, player)| player.handle);
+
+    let lines: Vec<_> = local_ships
+        .iter()
+        .map(|(ship_entity, player)| {
+            // A broadside is loaded once its slowest cannon is
+            let mut port_reload_frames = 0;
+            let mut starboard_reload_frames = 0;
+            for descendant in children_query.iter_descendants(*ship_entity) {
+                if let Ok(artillery) = artillery_query.get(descendant) {
+                    let reload_frames = match artillery.broadside {
+                        Broadside::Port => &mut port_reload_frames,
+                        Broadside::Starboard => &mut starboard_reload_frames,
+                    };
+                    *reload_frames = artillery.reload_frames.max(*reload_frames);
+                }
+            }
+
+            let broadsides = format!(
+                "Port {} | Starboard {}",
+                reload_bar(port_reload_frames),
+                reload_bar(starboard_reload_frames)
+            );
+
+            if local_ships.len() > 1 {
+                format!("Player {}: {broadsides}", player.handle + 1)
+            } else {
+                broadsides
+            }
+        })
+        .collect();
+
+    for mut text in &mut indicator_query {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+pub fn despawn_reload_indicator(
+    mut commands: Commands,
+    indicator_query: Query<Entity, With<ReloadIndicator>>,
+) {
+    for entity in &indicator_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocean::WAVES;
+
+    fn flat_water() -> Wave {
+        Wave {
+            time_scale: 1.,
+            sample_count: 4,
+            configs: [Vec4::new(1., 0., 0., 10.); 4],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn water_entry_is_where_the_path_crosses_flat_water() {
+        let entry_point =
+            water_entry_point(Vec3::new(0., 1., 0.), Vec3::new(2., -1., 0.), &flat_water());
+
+        // Within the last bisection step, 1/256 of the path
+        assert!(entry_point.abs_diff_eq(Vec3::new(1., 0., 0.), 0.01));
+    }
+
+    #[test]
+    fn water_entry_is_on_the_wave_surface() {
+        let mut wave = Wave {
+            time_scale: 1.,
+            sample_count: 4,
+            configs: WAVES,
+            ..default()
+        };
+        wave.advance(3.5);
+
+        for x in [-7., 0., 4., 11.] {
+            let entry_point =
+                water_entry_point(Vec3::new(x, 3., 2.), Vec3::new(x + 0.8, -3., 2.), &wave);
+            let surface_height = wave.height(entry_point, wave.configs, wave.phases);
+            assert!((entry_point.y - surface_height).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn flat_fast_projectiles_skip_until_they_run_out_of_skips() {
+        let velocity = Vec3::new(20., -2., 5.);
+
+        let skip_velocity = skip_velocity(velocity, 0).unwrap();
+        assert!(skip_velocity.y > 0.);
+        assert!(skip_velocity.xz().length() < velocity.xz().length());
+
+        assert!(super::skip_velocity(velocity, MAX_PROJECTILE_SKIPS).is_none());
+    }
+
+    #[test]
+    fn steep_or_slow_projectiles_splash_in() {
+        assert!(skip_velocity(Vec3::new(10., -10., 0.), 0).is_none());
+        assert!(skip_velocity(Vec3::new(MIN_SKIP_SPEED * 0.9, -0.1, 0.), 0).is_none());
+    }
+}
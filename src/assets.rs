@@ -1,21 +1,16 @@
+mod loaders;
 pub mod resources;
 mod systems;
 
-use crate::assets::systems::{add_assets, check_assets_ready};
+use crate::assets::loaders::{InputBindingsLoader, ShipyardLoader};
+use crate::assets::systems::{add_assets, check_assets_ready, display_assets_error};
 use crate::game_state::states::GameState;
+use crate::inputs::resources::InputBindings;
+use crate::player::definitions::Shipyard;
 use bevy::prelude::*;
 
-const MODEL_FILE_NAMES: [&str; 7] = [
-    "medium_flag.glb",
-    "medium_helm.glb",
-    "medium_hull.glb",
-    "medium_pirate_sail.glb",
-    "medium_canon.glb",
-    "raft_with_mast.glb",
-    "cannon_ball.glb",
-];
-
-const MESH_FILE_NAMES: [&str; 1] = ["medium_flag.glb"];
+// Models of the ships are listed in the shipyard and loaded along with it
+pub const MODEL_FILE_NAMES: [&str; 1] = ["cannon_ball.glb"];
 
 const FONT_FILE_NAMES: [&str; 1] = ["the-bomb-regular.otf"];
 
@@ -23,11 +18,21 @@ pub struct AssetsPlugin;
 
 impl Plugin for AssetsPlugin {
     fn build(&self, app: &mut App) {
+        app.init_asset::<Shipyard>();
+        app.init_asset_loader::<ShipyardLoader>();
+        app.init_asset::<InputBindings>();
+        app.init_asset_loader::<InputBindingsLoader>();
+
         app.add_systems(OnEnter(GameState::LoadingAssets), add_assets);
 
         app.add_systems(
             Update,
             check_assets_ready.run_if(in_state(GameState::LoadingAssets)),
         );
+
+        app.add_systems(
+            OnEnter(GameState::LoadingAssetsFailed),
+            display_assets_error,
+        );
     }
 }
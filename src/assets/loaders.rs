@@ -0,0 +1,149 @@
+use crate::inputs::resources::InputBindings;
+use crate::player::definitions::Shipyard;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::utils::BoxedFuture;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ShipyardLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    NoShips,
+}
+
+impl fmt::Display for ShipyardLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShipyardLoaderError::Io(error) => write!(f, "Could not read shipyard: {error}"),
+            ShipyardLoaderError::Ron(error) => write!(f, "Could not parse shipyard: {error}"),
+            ShipyardLoaderError::NoShips => write!(f, "Shipyard lists no ships"),
+        }
+    }
+}
+
+impl std::error::Error for ShipyardLoaderError {}
+
+impl From<std::io::Error> for ShipyardLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        ShipyardLoaderError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for ShipyardLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        ShipyardLoaderError::Ron(error)
+    }
+}
+
+// Loads the ship definitions along with the scenes of every model they are built from, so that
+// the shipyard only counts as loaded once ships can be spawned from it
+#[derive(Default)]
+pub struct ShipyardLoader;
+
+impl AssetLoader for ShipyardLoader {
+    type Asset = Shipyard;
+    type Settings = ();
+    type Error = ShipyardLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Shipyard, ShipyardLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let mut shipyard: Shipyard = ron::de::from_bytes(&bytes)?;
+            if !shipyard.has_ships() {
+                return Err(ShipyardLoaderError::NoShips);
+            }
+
+            for name in shipyard.model_file_names() {
+                let handle = load_context.load(format!("models/{name}#Scene0"));
+                shipyard.scene_handles.insert(name, handle);
+            }
+
+            for name in shipyard.flag_model_file_names() {
+                let handle = load_context.load(format!("models/{name}#Mesh0/Primitive0"));
+                shipyard.flag_mesh_handles.insert(name, handle);
+            }
+
+            Ok(shipyard)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ships.ron"]
+    }
+}
+
+#[derive(Debug)]
+pub enum InputBindingsLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    NoPlayers,
+}
+
+impl fmt::Display for InputBindingsLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputBindingsLoaderError::Io(error) => {
+                write!(f, "Could not read key bindings: {error}")
+            }
+            InputBindingsLoaderError::Ron(error) => {
+                write!(f, "Could not parse key bindings: {error}")
+            }
+            InputBindingsLoaderError::NoPlayers => write!(f, "Key bindings list no players"),
+        }
+    }
+}
+
+impl std::error::Error for InputBindingsLoaderError {}
+
+impl From<std::io::Error> for InputBindingsLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        InputBindingsLoaderError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for InputBindingsLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        InputBindingsLoaderError::Ron(error)
+    }
+}
+
+// Loads the default key bindings, which the bindings a player saved are checked against
+#[derive(Default)]
+pub struct InputBindingsLoader;
+
+impl AssetLoader for InputBindingsLoader {
+    type Asset = InputBindings;
+    type Settings = ();
+    type Error = InputBindingsLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<InputBindings, InputBindingsLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let bindings: InputBindings = ron::de::from_bytes(&bytes)?;
+            if bindings.players.is_empty() {
+                return Err(InputBindingsLoaderError::NoPlayers);
+            }
+
+            Ok(bindings)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bindings.ron"]
+    }
+}
@@ -1,13 +1,28 @@
+use crate::inputs::resources::InputBindings;
+use crate::player::definitions::Shipyard;
 use bevy::prelude::*;
 use std::collections::HashMap;
 
 #[derive(Resource, Default)]
 pub struct ModelAssets {
     pub scene_handles: HashMap<&'static str, Handle<Scene>>,
-    pub mesh_handles: HashMap<&'static str, Handle<Mesh>>,
 }
 
 #[derive(Resource, Default)]
 pub struct FontAssets {
     pub font_handles: HashMap<&'static str, Handle<Font>>,
 }
+
+#[derive(Resource)]
+pub struct ShipyardAsset {
+    pub handle: Handle<Shipyard>,
+}
+
+#[derive(Resource)]
+pub struct InputBindingsAsset {
+    pub handle: Handle<InputBindings>,
+}
+
+// Reason for failing to load the assets, shown to the player
+#[derive(Resource)]
+pub struct AssetsError(pub String);
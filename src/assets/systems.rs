@@ -1,13 +1,19 @@
-use crate::assets::resources::{FontAssets, ModelAssets};
-use crate::assets::{FONT_FILE_NAMES, MESH_FILE_NAMES, MODEL_FILE_NAMES};
+use crate::assets::resources::{
+    AssetsError, FontAssets, InputBindingsAsset, ModelAssets, ShipyardAsset,
+};
+use crate::assets::{FONT_FILE_NAMES, MODEL_FILE_NAMES};
+use crate::camera::resources::MainCamera;
+use crate::game_state::error_screen::spawn_error_screen;
 use crate::game_state::states::GameState;
-use bevy::asset::LoadState;
+use crate::inputs::INPUT_BINDINGS_FILE_NAME;
+use crate::player::definitions::Shipyard;
+use crate::player::SHIPYARD_FILE_NAME;
+use bevy::asset::{LoadState, RecursiveDependencyLoadState};
 use bevy::prelude::*;
 use std::collections::HashMap;
 
 pub fn add_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     let mut scene_handles = HashMap::new();
-    let mut mesh_handles = HashMap::new();
     let mut font_handles = HashMap::new();
 
     for name in MODEL_FILE_NAMES {
@@ -15,27 +21,31 @@ pub fn add_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
         scene_handles.insert(name, handle);
     }
 
-    for name in MESH_FILE_NAMES {
-        let handle = asset_server.load(format!("models/{name}#Mesh0/Primitive0"));
-        mesh_handles.insert(name, handle);
-    }
-
     for name in FONT_FILE_NAMES {
         let handle = asset_server.load(format!("fonts/{name}"));
         font_handles.insert(name, handle);
     }
 
-    commands.insert_resource(ModelAssets {
-        scene_handles,
-        mesh_handles,
-    });
+    commands.insert_resource(ModelAssets { scene_handles });
 
     commands.insert_resource(FontAssets { font_handles });
+
+    commands.insert_resource(ShipyardAsset {
+        handle: asset_server.load(SHIPYARD_FILE_NAME),
+    });
+
+    commands.insert_resource(InputBindingsAsset {
+        handle: asset_server.load(INPUT_BINDINGS_FILE_NAME),
+    });
 }
 
 pub fn check_assets_ready(
+    mut commands: Commands,
     model_assets: Res<ModelAssets>,
     font_assets: Res<FontAssets>,
+    shipyard_asset: Res<ShipyardAsset>,
+    shipyards: Res<Assets<Shipyard>>,
+    input_bindings_asset: Res<InputBindingsAsset>,
     asset_server: Res<AssetServer>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
@@ -65,7 +75,51 @@ pub fn check_assets_ready(
         }
     }
 
+    // The shipyard and the key bindings are edited by hand, so a mistake in them, or in the models
+    // the shipyard lists, must not leave the game loading forever
+    if asset_server.get_load_state(shipyard_asset.handle.id()) == Some(LoadState::Failed)
+        || asset_server.get_recursive_dependency_load_state(shipyard_asset.handle.id())
+            == Some(RecursiveDependencyLoadState::Failed)
+    {
+        fail_loading(&mut commands, &mut next_state, SHIPYARD_FILE_NAME);
+        return;
+    }
+
+    if !asset_server.is_loaded_with_dependencies(shipyard_asset.handle.id()) {
+        all_loaded = false;
+    }
+
+    if asset_server.get_load_state(input_bindings_asset.handle.id()) == Some(LoadState::Failed) {
+        fail_loading(&mut commands, &mut next_state, INPUT_BINDINGS_FILE_NAME);
+        return;
+    }
+
+    if !asset_server.is_loaded_with_dependencies(input_bindings_asset.handle.id()) {
+        all_loaded = false;
+    }
+
     if all_loaded {
+        // Ships are spawned from the shipyard resource, which is also available when running
+        // headless
+        if let Some(shipyard) = shipyards.get(shipyard_asset.handle.id()) {
+            commands.insert_resource(shipyard.clone());
+        }
+
         next_state.set(GameState::SplashScreen);
     }
 }
+
+fn fail_loading(commands: &mut Commands, next_state: &mut NextState<GameState>, file_name: &str) {
+    let message = format!("Failed to load {file_name}");
+    error!("{message}");
+    commands.insert_resource(AssetsError(message));
+    next_state.set(GameState::LoadingAssetsFailed);
+}
+
+pub fn display_assets_error(
+    mut commands: Commands,
+    main_camera: Res<MainCamera>,
+    assets_error: Res<AssetsError>,
+) {
+    spawn_error_screen(&mut commands, &main_camera, &assets_error.0);
+}
@@ -1,9 +1,11 @@
 use crate::camera::systems::{
-    grab_pointer, release_pointer, release_pointer_on_escape, spawn_camera,
+    despawn_split_screen_cameras, grab_pointer, release_pointer, release_pointer_on_escape,
+    spawn_camera, sync_split_screen_cameras, update_split_screen_viewports,
 };
 use crate::game_state::states::GameState;
 use bevy::prelude::*;
 
+mod components;
 pub mod resources;
 pub mod systems;
 
@@ -16,6 +18,15 @@ impl Plugin for CameraPlugin {
         app.add_systems(OnEnter(GameState::InGame), grab_pointer);
         app.add_systems(OnExit(GameState::InGame), release_pointer);
 
+        // Several players on one machine each get their own part of the window
+        app.add_systems(
+            Update,
+            (sync_split_screen_cameras, update_split_screen_viewports)
+                .chain()
+                .run_if(in_state(GameState::InGame)),
+        );
+        app.add_systems(OnExit(GameState::InGame), despawn_split_screen_cameras);
+
         app.add_systems(
             Update,
             release_pointer_on_escape.run_if(in_state(GameState::InGame)),
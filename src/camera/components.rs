@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// Cameras of local players other than the first, which share the window with the main camera
+#[derive(Component)]
+pub struct SplitScreenCamera;
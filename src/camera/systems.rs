@@ -1,40 +1,158 @@
+use crate::camera::components::SplitScreenCamera;
 use crate::camera::resources::MainCamera;
+use crate::focal_point::components::CameraFocalPoint;
 use crate::ocean::OCEAN_TILE_SIZE;
 use crate::orbiting_camera::resources::OrbitingCamera;
 use bevy::prelude::*;
+use bevy::render::camera::Viewport;
 use bevy::window::{Cursor, CursorGrabMode};
+use bevy_ggrs::LocalPlayers;
 
-pub fn spawn_camera(mut commands: Commands) {
+fn orbiting_camera_bundle(local_player: usize, camera: Camera) -> impl Bundle {
     let pitch = 30_f32.to_radians();
     let radius = 30. + 15. * pitch;
     let translation = Vec3::new(0.0, pitch.sin() * radius, pitch.cos() * radius);
 
-    let main_camera_id = commands
-        .spawn((
-            OrbitingCamera {
-                pitch,
-                radius,
-                ..default()
-            },
-            Camera3dBundle {
-                transform: Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y),
-                ..default()
+    (
+        OrbitingCamera {
+            local_player,
+            pitch,
+            radius,
+            ..default()
+        },
+        CameraFocalPoint::default(),
+        Camera3dBundle {
+            camera,
+            transform: Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        FogSettings {
+            color: Color::hex("a5cddf").unwrap(),
+            directional_light_color: Color::rgba(1.0, 0.95, 0.85, 0.5),
+            directional_light_exponent: 30.0,
+            falloff: FogFalloff::Linear {
+                start: OCEAN_TILE_SIZE * 0.25,
+                end: OCEAN_TILE_SIZE * 1.5,
             },
-            FogSettings {
-                color: Color::hex("a5cddf").unwrap(),
-                directional_light_color: Color::rgba(1.0, 0.95, 0.85, 0.5),
-                directional_light_exponent: 30.0,
-                falloff: FogFalloff::Linear {
-                    start: OCEAN_TILE_SIZE * 0.25,
-                    end: OCEAN_TILE_SIZE * 1.5,
-                },
-            },
-        ))
+        },
+    )
+}
+
+pub fn spawn_camera(mut commands: Commands) {
+    let main_camera_id = commands
+        .spawn(orbiting_camera_bundle(0, Camera::default()))
         .id();
 
     commands.insert_resource(MainCamera { id: main_camera_id });
 }
 
+// Gives every local player a camera following their own ship. The main camera belongs to the first
+// local player, and is left to the spectator systems when there are no local players. Cameras pick
+// up the ship to follow the update after they are spawned.
+pub fn sync_split_screen_cameras(
+    mut commands: Commands,
+    local_players: Res<LocalPlayers>,
+    mut camera_query: Query<(
+        Entity,
+        &OrbitingCamera,
+        &mut CameraFocalPoint,
+        Has<SplitScreenCamera>,
+    )>,
+) {
+    let mut has_camera = vec![false; local_players.0.len()];
+
+    for (entity, orbiting_camera, mut camera_focal_point, is_split_screen_camera) in
+        &mut camera_query
+    {
+        match local_players.0.get(orbiting_camera.local_player) {
+            Some(handle) => {
+                camera_focal_point.handle = *handle;
+                has_camera[orbiting_camera.local_player] = true;
+            }
+            None if is_split_screen_camera => commands.entity(entity).despawn_recursive(),
+            None => {}
+        }
+    }
+
+    for (local_player, has_camera) in has_camera.into_iter().enumerate() {
+        if has_camera {
+            continue;
+        }
+
+        let camera = Camera {
+            order: local_player as isize,
+            // Only the main camera clears the window, or it would clear the other viewports
+            clear_color: ClearColorConfig::None,
+            ..default()
+        };
+
+        commands.spawn((
+            orbiting_camera_bundle(local_player, camera),
+            SplitScreenCamera,
+            Name::new(format!("Split screen camera {}", local_player + 1)),
+        ));
+    }
+}
+
+// Tiles the window with one viewport per local player, in as square a grid as possible
+pub fn update_split_screen_viewports(
+    window_query: Query<&Window>,
+    local_players: Res<LocalPlayers>,
+    mut camera_query: Query<(&OrbitingCamera, &mut Camera)>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    let num_viewports = local_players.0.len() as u32;
+    let columns = (num_viewports as f32).sqrt().ceil().max(1.) as u32;
+    let rows = num_viewports.div_ceil(columns).max(1);
+    let viewport_size = UVec2::new(
+        window.physical_width() / columns,
+        window.physical_height() / rows,
+    );
+
+    // Minimized windows have no room for viewports
+    if viewport_size.cmpeq(UVec2::ZERO).any() {
+        return;
+    }
+
+    for (orbiting_camera, mut camera) in &mut camera_query {
+        let viewport = (num_viewports > 1).then(|| {
+            let index = orbiting_camera.local_player as u32;
+            Viewport {
+                physical_position: UVec2::new(index % columns, index / columns) * viewport_size,
+                physical_size: viewport_size,
+                ..default()
+            }
+        });
+
+        // Viewports don't implement PartialEq, compare their rectangles instead
+        let rectangle = |viewport: &Option<Viewport>| {
+            viewport
+                .as_ref()
+                .map(|viewport| (viewport.physical_position, viewport.physical_size))
+        };
+        if rectangle(&camera.viewport) != rectangle(&viewport) {
+            camera.viewport = viewport;
+        }
+    }
+}
+
+pub fn despawn_split_screen_cameras(
+    mut commands: Commands,
+    split_screen_camera_query: Query<Entity, With<SplitScreenCamera>>,
+    mut camera_query: Query<&mut Camera>,
+) {
+    for entity in &split_screen_camera_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    for mut camera in &mut camera_query {
+        camera.viewport = None;
+    }
+}
+
 pub fn grab_pointer(mut window: Query<&mut Window>) {
     if let Ok(mut window) = window.get_single_mut() {
         window.cursor = Cursor {
@@ -1,14 +1,26 @@
 use crate::args::run_conditions::p2p_mode;
-use crate::connection::systems::{start_matchbox_socket, wait_for_players};
+use crate::connection::systems::{
+    despawn_lobby_status, display_connection_error, spawn_lobby_status, start_matchbox_socket,
+    update_lobby_status, wait_for_players,
+};
 use crate::game_state::states::GameState;
 use bevy::prelude::*;
+use bevy_matchbox::prelude::*;
 
+mod components;
+pub mod resources;
 pub mod systems;
 
 pub const MAX_PREDICTION: usize = 12;
 pub const FPS: usize = 60;
 pub const INPUT_DELAY: usize = 2;
 
+pub const GGRS_CHANNEL: usize = 0;
+pub const ROLE_CHANNEL: usize = 1;
+
+pub const PLAYER_ROLE: u8 = 0;
+pub const SPECTATOR_ROLE: u8 = 1;
+
 pub struct ConnectionPlugin;
 
 impl Plugin for ConnectionPlugin {
@@ -18,9 +30,21 @@ impl Plugin for ConnectionPlugin {
             start_matchbox_socket.run_if(p2p_mode),
         );
 
+        // Peers wait in the lobby until the room is full
+        app.add_systems(OnEnter(GameState::Lobby), spawn_lobby_status);
+        app.add_systems(OnExit(GameState::Lobby), despawn_lobby_status);
+
         app.add_systems(
             Update,
-            wait_for_players.run_if(in_state(GameState::Matchmaking).and_then(p2p_mode)),
+            (wait_for_players, update_lobby_status).chain().run_if(
+                in_state(GameState::Lobby)
+                    .and_then(resource_exists::<MatchboxSocket<MultipleChannels>>),
+            ),
+        );
+
+        app.add_systems(
+            OnEnter(GameState::ConnectionFailed),
+            display_connection_error,
         );
     }
 }
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_matchbox::prelude::PeerId;
+
+// Reason for failing to start an online session, shown to the player
+#[derive(Resource)]
+pub struct ConnectionError(pub String);
+
+// Whether each connected peer is a spectator, as announced by the peer itself
+#[derive(Resource, Default)]
+pub struct PeerRoles(pub HashMap<PeerId, bool>);
+
+// Ship class each connected peer picked in the menu, announced along with its role
+#[derive(Resource, Default)]
+pub struct PeerShipClasses(pub HashMap<PeerId, String>);
@@ -1,42 +1,193 @@
 use crate::args::resources::Args;
-use crate::connection::{FPS, INPUT_DELAY, MAX_PREDICTION};
+use crate::assets::resources::FontAssets;
+use crate::camera::resources::MainCamera;
+use crate::connection::components::LobbyStatus;
+use crate::connection::resources::{ConnectionError, PeerRoles, PeerShipClasses};
+use crate::connection::{
+    FPS, GGRS_CHANNEL, INPUT_DELAY, MAX_PREDICTION, PLAYER_ROLE, ROLE_CHANNEL, SPECTATOR_ROLE,
+};
+use crate::game_state::error_screen::spawn_error_screen;
 use crate::game_state::states::GameState;
+use crate::inputs::PlayerInput;
+use crate::player::definitions::Shipyard;
+use crate::player::resources::ShipClasses;
 use bevy::prelude::*;
 use bevy_ggrs::{ggrs, GgrsConfig};
 use bevy_matchbox::prelude::*;
 
-// The first generic parameter, u8, is the input type: 4-directions + fire fits
-// easily in a single byte
+// The first generic parameter, PlayerInput, is the input type: the buttons fit in a single byte,
+// next to a quantized analog steering axis
 // The second parameter is the address type of peers: Matchbox' WebRtcSocket
 // addresses are called `PeerId`s
-pub type RollbackConfig = GgrsConfig<u8, PeerId>;
+pub type RollbackConfig = GgrsConfig<PlayerInput, PeerId>;
+
+pub fn start_matchbox_socket(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    args: Res<Args>,
+) {
+    let room_url = match args.room_url() {
+        Ok(room_url) => room_url,
+        Err(room_url_error) => {
+            error!("{room_url_error}");
+            commands.insert_resource(ConnectionError(room_url_error.to_string()));
+            next_state.set(GameState::ConnectionFailed);
+            return;
+        }
+    };
 
-pub fn start_matchbox_socket(mut commands: Commands) {
-    let room_url = "ws://192.168.100.158:3536/rogue_waves?next=2";
     info!("connecting to matchbox server: {room_url}");
-    commands.insert_resource(MatchboxSocket::new_ggrs(room_url));
+
+    // Unreliable channel for GGRS and a reliable one for telling peers whether we play or watch
+    let socket: MatchboxSocket<MultipleChannels> = WebRtcSocketBuilder::new(room_url)
+        .add_channel(ChannelConfig::ggrs())
+        .add_channel(ChannelConfig::reliable())
+        .into();
+
+    commands.insert_resource(socket);
+    commands.insert_resource(PeerRoles::default());
+    commands.insert_resource(PeerShipClasses::default());
+    next_state.set(GameState::Lobby);
 }
 
 pub fn wait_for_players(
     mut commands: Commands,
-    mut socket: ResMut<MatchboxSocket<SingleChannel>>,
+    mut socket: ResMut<MatchboxSocket<MultipleChannels>>,
+    mut peer_roles: ResMut<PeerRoles>,
+    mut peer_ship_classes: ResMut<PeerShipClasses>,
     mut next_state: ResMut<NextState<GameState>>,
     args: Res<Args>,
+    shipyard: Res<Shipyard>,
 ) {
-    if socket.get_channel(0).is_err() {
+    if socket.get_channel(GGRS_CHANNEL).is_err() {
         return; // we've already started
     }
 
-    // Check for new connections
-    socket.update_peers();
-    let players = socket.players();
+    // Check for new connections. The socket closes if the signaling server can't be reached.
+    let Ok(peer_changes) = socket.try_update_peers() else {
+        fail_connection(
+            &mut commands,
+            &mut next_state,
+            format!(
+                "Could not connect to matchbox server {}",
+                args.matchbox_server_url
+            ),
+        );
+        return;
+    };
+
+    let local_role = if args.spectate {
+        SPECTATOR_ROLE
+    } else {
+        PLAYER_ROLE
+    };
+
+    // The role is followed by the ship class picked in the menu
+    let announcement: Box<[u8]> = [local_role].into_iter().chain(args.hull.bytes()).collect();
+
+    for (peer, state) in peer_changes {
+        if state == PeerState::Connected {
+            socket
+                .channel_mut(ROLE_CHANNEL)
+                .send(announcement.clone(), peer);
+        }
+    }
+
+    for (peer, packet) in socket.channel_mut(ROLE_CHANNEL).receive() {
+        peer_roles
+            .0
+            .insert(peer, packet.first() == Some(&SPECTATOR_ROLE));
+
+        let ship_class = packet.get(1..).unwrap_or_default();
+        peer_ship_classes
+            .0
+            .insert(peer, String::from_utf8_lossy(ship_class).into_owned());
+    }
 
-    if players.len() < args.num_players {
+    let peers = socket.players();
+
+    if peers.len() < args.num_players + args.num_spectators {
         return; // wait for more players
     }
 
+    // Player handles need to be assigned in the same order on all peers, which the peer order
+    // returned from the socket guarantees
+    let mut players = Vec::new();
+    let mut spectators = Vec::new();
+    for peer in peers {
+        match peer {
+            ggrs::PlayerType::Local if args.spectate => {}
+            ggrs::PlayerType::Local => players.push(peer),
+            ggrs::PlayerType::Remote(peer_id) => match peer_roles.0.get(&peer_id) {
+                Some(true) => spectators.push(ggrs::PlayerType::Spectator(peer_id)),
+                Some(false) => players.push(peer),
+                None => return, // wait for the role of the peer
+            },
+            ggrs::PlayerType::Spectator(_) => {}
+        }
+    }
+
+    if players.len() != args.num_players {
+        fail_connection(
+            &mut commands,
+            &mut next_state,
+            format!(
+                "Expected {} players in room {}, got {}",
+                args.num_players,
+                args.room,
+                players.len()
+            ),
+        );
+        return;
+    }
+
     info!("All peers have joined, going in-game");
 
+    // Every peer lists the classes in handle order, so that all of them spawn the same ships. A class
+    // missing from the shipyard of any peer would have it spawn another ship, so the match can't
+    // start. Names are listed the way the shipyard spells them, so that case can't differ either.
+    let mut ship_classes = Vec::new();
+    for player in &players {
+        let (owner, ship_class) = match player {
+            ggrs::PlayerType::Remote(peer_id) => {
+                (format!("peer {peer_id}"), &peer_ship_classes.0[peer_id])
+            }
+            _ => ("this peer".to_string(), &args.hull),
+        };
+
+        let Some(ship) = shipyard.find_ship(ship_class) else {
+            fail_connection(
+                &mut commands,
+                &mut next_state,
+                format!("Ship class {ship_class} of {owner} is not in the shipyard"),
+            );
+            return;
+        };
+
+        ship_classes.push(ship.name.clone());
+    }
+    commands.insert_resource(ShipClasses(ship_classes));
+
+    // move the channel out of the socket (required because GGRS takes ownership of it)
+    let channel = socket.take_channel(GGRS_CHANNEL).unwrap();
+
+    if args.spectate {
+        // The player with the first handle hosts the spectators
+        let Some(ggrs::PlayerType::Remote(host)) = players.first().copied() else {
+            unreachable!("spectators only see remote players");
+        };
+
+        let ggrs_session = ggrs::SessionBuilder::<RollbackConfig>::new()
+            .with_num_players(args.num_players)
+            .with_fps(FPS)
+            .expect("FPS can't be 0")
+            .start_spectator_session(host, channel);
+
+        commands.insert_resource(bevy_ggrs::Session::Spectator(ggrs_session));
+        next_state.set(GameState::InGame);
+        return;
+    }
+
     // create a GGRS P2P session
     let mut session_builder = ggrs::SessionBuilder::<RollbackConfig>::new()
         .with_num_players(args.num_players)
@@ -51,14 +202,22 @@ pub fn wait_for_players(
         // (optional) set input delay for the local player
         .with_input_delay(INPUT_DELAY);
 
+    let is_host = players.first() == Some(&ggrs::PlayerType::Local);
+
     for (i, player) in players.into_iter().enumerate() {
         session_builder = session_builder
             .add_player(player, i)
             .expect("failed to add player");
     }
 
-    // move the channel out of the socket (required because GGRS takes ownership of it)
-    let channel = socket.take_channel(0).unwrap();
+    // Spectators only connect to the host, and get the handles following the players
+    if is_host {
+        for (i, spectator) in spectators.into_iter().enumerate() {
+            session_builder = session_builder
+                .add_player(spectator, args.num_players + i)
+                .expect("failed to add spectator");
+        }
+    }
 
     // start the GGRS session
     let ggrs_session = session_builder
@@ -68,3 +227,83 @@ pub fn wait_for_players(
     commands.insert_resource(bevy_ggrs::Session::P2P(ggrs_session));
     next_state.set(GameState::InGame);
 }
+
+fn fail_connection(
+    commands: &mut Commands,
+    next_state: &mut NextState<GameState>,
+    message: String,
+) {
+    error!("{message}");
+    commands.insert_resource(ConnectionError(message));
+    commands.remove_resource::<MatchboxSocket<MultipleChannels>>();
+    next_state.set(GameState::ConnectionFailed);
+}
+
+pub fn display_connection_error(
+    mut commands: Commands,
+    main_camera: Res<MainCamera>,
+    connection_error: Res<ConnectionError>,
+) {
+    spawn_error_screen(&mut commands, &main_camera, &connection_error.0);
+}
+
+pub fn spawn_lobby_status(
+    mut commands: Commands,
+    main_camera: Res<MainCamera>,
+    font_assets: Res<FontAssets>,
+) {
+    commands
+        .spawn((
+            // Seems to be required in dev builds since using editor plugin results in multiple
+            // cameras
+            TargetCamera(main_camera.id),
+            LobbyStatus,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|child_builder| {
+            child_builder.spawn(TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: font_assets.font_handles["the-bomb-regular.otf"].clone(),
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+pub fn update_lobby_status(
+    mut socket: ResMut<MatchboxSocket<MultipleChannels>>,
+    args: Res<Args>,
+    lobby_status_query: Query<&Children, With<LobbyStatus>>,
+    mut text_query: Query<&mut Text>,
+) {
+    let joined = socket.players().len();
+    let expected = args.num_players + args.num_spectators;
+
+    for children in &lobby_status_query {
+        for child in children {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = format!("Room {}: {joined}/{expected} joined", args.room);
+            }
+        }
+    }
+}
+
+pub fn despawn_lobby_status(
+    mut commands: Commands,
+    lobby_status_query: Query<Entity, With<LobbyStatus>>,
+) {
+    for entity in &lobby_status_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,4 +1,8 @@
-use crate::controls::components::{checksum_wheel_turn_ratio, WheelTurnRatio};
+use crate::controls::components::{
+    checksum_sail_canvas_ratio, checksum_sail_trim_ratio, checksum_wheel_turn_ratio, Controls,
+    SailCanvasRatio, SailTrimRatio, WheelTurnRatio,
+};
+use crate::desync_report::ChecksumReportApp;
 use bevy::prelude::*;
 use bevy_ggrs::GgrsApp;
 
@@ -10,7 +14,13 @@ impl Plugin for ShipPlugin {
     fn build(&self, app: &mut App) {
         // Component candidates for roll back
         app.rollback_component_with_copy::<WheelTurnRatio>();
+        app.rollback_component_with_copy::<SailTrimRatio>();
+        app.rollback_component_with_copy::<SailCanvasRatio>();
+        app.rollback_component_with_copy::<Controls>();
 
-        app.checksum_component::<WheelTurnRatio>(checksum_wheel_turn_ratio);
+        app.checksum_and_report_component::<WheelTurnRatio>(checksum_wheel_turn_ratio);
+        app.checksum_and_report_component::<SailTrimRatio>(checksum_sail_trim_ratio);
+        app.checksum_and_report_component::<SailCanvasRatio>(checksum_sail_canvas_ratio);
+        app.checksum_and_report_component_with_hash::<Controls>();
     }
 }
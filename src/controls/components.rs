@@ -9,7 +9,12 @@ pub struct WheelTurnRatio(pub f32);
 #[reflect(Component)]
 pub struct SailTrimRatio(pub f32);
 
-#[derive(Component, Reflect, Clone, Copy, Default)]
+// How much of the sail is let out, from fully reefed at 0 to full canvas at 1
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct SailCanvasRatio(pub f32);
+
+#[derive(Component, Reflect, Clone, Copy, Default, Hash)]
 #[reflect(Component)]
 pub struct Controls {
     pub turn_action: i32,
@@ -19,3 +24,11 @@ pub struct Controls {
 pub fn checksum_wheel_turn_ratio(value: &WheelTurnRatio) -> u64 {
     hash_f32_number(value.0)
 }
+
+pub fn checksum_sail_trim_ratio(value: &SailTrimRatio) -> u64 {
+    hash_f32_number(value.0)
+}
+
+pub fn checksum_sail_canvas_ratio(value: &SailCanvasRatio) -> u64 {
+    hash_f32_number(value.0)
+}
@@ -0,0 +1,55 @@
+pub mod components;
+mod systems;
+
+use crate::artillery::systems::despawn_hit_projectiles;
+use crate::damage::components::{
+    checksum_hull_integrity, checksum_sinking, HullIntegrity, Sinking,
+};
+use crate::damage::systems::{damage_hit_ships, flood_hulls, sink_ships, start_sinking};
+use crate::desync_report::ChecksumReportApp;
+use crate::physics::systems::update_linear_velocity;
+use bevy::prelude::*;
+use bevy_ggrs::{GgrsApp, GgrsSchedule};
+
+pub const MAX_HULL_INTEGRITY: f32 = 100.;
+
+// Hull integrity lost per meter per second of speed a projectile hits with
+pub const DAMAGE_PER_IMPACT_SPEED: f32 = 1.5;
+
+// Share of a column flooded per second, per point of damage the leak was caused with
+pub const LEAK_INFLOW_PER_DAMAGE: f32 = 0.002;
+
+// Distance from a leak at which its water no longer reaches
+pub const FLOODING_RADIUS: f32 = 2.5;
+
+// Columns keep some air, so that a flooded hull still floats until it gives in
+pub const MAX_COLUMN_FLOODING: f32 = 0.8;
+
+// Share of hull buoyancy lost per second by sinking ships
+pub const SINKING_RATE: f32 = 0.2;
+
+// Time from a hull giving in until the wreck is removed from the match
+pub const SINKING_SECONDS: f32 = 8.;
+
+// Ships take damage from projectile hits, flood through the holes and sink once their hull gives
+// in
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        // Flooding and sinking lift the hull less from the next frame on
+        app.add_systems(
+            GgrsSchedule,
+            (damage_hit_ships, start_sinking, flood_hulls, sink_ships)
+                .chain()
+                .after(despawn_hit_projectiles)
+                .before(update_linear_velocity),
+        );
+
+        app.rollback_component_with_clone::<HullIntegrity>();
+        app.rollback_component_with_copy::<Sinking>();
+
+        app.checksum_and_report_component::<HullIntegrity>(checksum_hull_integrity);
+        app.checksum_and_report_component::<Sinking>(checksum_sinking);
+    }
+}
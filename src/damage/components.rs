@@ -0,0 +1,94 @@
+use crate::damage::{FLOODING_RADIUS, MAX_HULL_INTEGRITY};
+use crate::utils::hash::{hash_f32_number, hash_vec3};
+use bevy::prelude::*;
+use bevy::utils::FixedState;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+// Hole in the hull letting water in
+#[derive(Reflect, Clone, Copy, Default, Debug)]
+pub struct Leak {
+    // Where the hull was hit, in ship space
+    pub position: Vec3,
+    // Share of a column flooded per second right at the leak
+    pub inflow: f32,
+}
+
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct HullIntegrity {
+    pub health: f32,
+    pub leaks: Vec<Leak>,
+}
+
+impl Default for HullIntegrity {
+    fn default() -> Self {
+        Self {
+            health: MAX_HULL_INTEGRITY,
+            leaks: Vec::new(),
+        }
+    }
+}
+
+impl HullIntegrity {
+    // Hits within the reach of an earlier leak widen it rather than opening another, which keeps
+    // the leaks of a hull as few as there are spots on it that far apart
+    pub fn add_leak(&mut self, position: Vec3, inflow: f32) {
+        match self
+            .leaks
+            .iter_mut()
+            .find(|leak| leak.position.distance(position) < FLOODING_RADIUS)
+        {
+            Some(leak) => leak.inflow += inflow,
+            None => self.leaks.push(Leak { position, inflow }),
+        }
+    }
+}
+
+// Ships whose hull gave in, out of control and going down
+#[derive(Component, Reflect, Clone, Copy, Default, Debug)]
+#[reflect(Component)]
+pub struct Sinking {
+    pub elapsed_seconds: f32,
+}
+
+pub fn checksum_hull_integrity(value: &HullIntegrity) -> u64 {
+    let mut hasher = FixedState.build_hasher();
+    hash_f32_number(value.health).hash(&mut hasher);
+    for leak in &value.leaks {
+        hash_vec3(leak.position).hash(&mut hasher);
+        hash_f32_number(leak.inflow).hash(&mut hasher);
+    }
+
+    #[allow(clippy::manual_hash_one)]
+    hasher.finish()
+}
+
+pub fn checksum_sinking(value: &Sinking) -> u64 {
+    hash_f32_number(value.elapsed_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hits_at_one_spot_widen_a_single_leak() {
+        let mut hull_integrity = HullIntegrity::default();
+        for hit in 0..100 {
+            let offset = Vec3::new(0.01 * (hit % 10) as f32, 0., 0.);
+            hull_integrity.add_leak(Vec3::new(1., 0.5, 2.) + offset, 0.01);
+        }
+
+        assert_eq!(hull_integrity.leaks.len(), 1);
+        assert!((hull_integrity.leaks[0].inflow - 1.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn hits_far_apart_open_separate_leaks() {
+        let mut hull_integrity = HullIntegrity::default();
+        hull_integrity.add_leak(Vec3::new(0., 0., -3.), 0.01);
+        hull_integrity.add_leak(Vec3::new(0., 0., 3.), 0.01);
+
+        assert_eq!(hull_integrity.leaks.len(), 2);
+    }
+}
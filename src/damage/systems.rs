@@ -0,0 +1,104 @@
+use crate::artillery::events::ProjectileHit;
+use crate::damage::components::{HullIntegrity, Sinking};
+use crate::damage::{
+    DAMAGE_PER_IMPACT_SPEED, FLOODING_RADIUS, LEAK_INFLOW_PER_DAMAGE, MAX_COLUMN_FLOODING,
+    SINKING_RATE, SINKING_SECONDS,
+};
+use crate::physics::components::{HullBuoyancy, LinearVelocity};
+use crate::player::components::Player;
+use crate::simulation::resources::SimulationClock;
+use bevy::prelude::*;
+use bevy_ggrs::Rollback;
+
+// Projectiles do more damage the faster they hit, relative to the ship they hit
+pub fn damage_hit_ships(
+    mut projectile_hit_event_reader: EventReader<ProjectileHit>,
+    mut ship_query: Query<
+        (
+            &Player,
+            &GlobalTransform,
+            &LinearVelocity,
+            &mut HullIntegrity,
+        ),
+        With<Rollback>,
+    >,
+) {
+    for hit in projectile_hit_event_reader.read() {
+        for (player, global_transform, linear_velocity, mut hull_integrity) in &mut ship_query {
+            if player.handle != hit.target {
+                continue;
+            }
+
+            let impact_speed = (hit.velocity - linear_velocity.0).length();
+            let damage = impact_speed * DAMAGE_PER_IMPACT_SPEED;
+
+            hull_integrity.health = (hull_integrity.health - damage).max(0.);
+            hull_integrity.add_leak(
+                global_transform
+                    .affine()
+                    .inverse()
+                    .transform_point3(hit.point),
+                damage * LEAK_INFLOW_PER_DAMAGE,
+            );
+        }
+    }
+}
+
+// Water leaking in floods the columns around each leak, so that ships list towards where they
+// were hit
+pub fn flood_hulls(
+    mut ship_query: Query<(&HullIntegrity, &mut HullBuoyancy), With<Rollback>>,
+    clock: Res<SimulationClock>,
+) {
+    for (hull_integrity, mut hull_buoyancy) in &mut ship_query {
+        if hull_integrity.leaks.is_empty() {
+            continue;
+        }
+
+        let hull_buoyancy = &mut *hull_buoyancy;
+        for (column, flooding) in hull_buoyancy
+            .columns
+            .iter()
+            .zip(&mut hull_buoyancy.flooding)
+        {
+            let column_position = Vec2::new(column.x, column.z);
+            let inflow: f32 = hull_integrity
+                .leaks
+                .iter()
+                .map(|leak| {
+                    let distance = column_position.distance(leak.position.xz());
+                    leak.inflow * (1. - distance / FLOODING_RADIUS).max(0.)
+                })
+                .sum();
+
+            *flooding = (*flooding + inflow * clock.delta_seconds).min(MAX_COLUMN_FLOODING);
+        }
+    }
+}
+
+pub fn start_sinking(
+    mut commands: Commands,
+    ship_query: Query<(Entity, &HullIntegrity), (With<Rollback>, Without<Sinking>)>,
+) {
+    for (entity, hull_integrity) in &ship_query {
+        if hull_integrity.health <= 0. {
+            commands.entity(entity).insert(Sinking::default());
+        }
+    }
+}
+
+pub fn sink_ships(
+    mut commands: Commands,
+    mut ship_query: Query<(Entity, &mut Sinking, &mut HullBuoyancy), With<Rollback>>,
+    clock: Res<SimulationClock>,
+) {
+    for (entity, mut sinking, mut hull_buoyancy) in &mut ship_query {
+        sinking.elapsed_seconds += clock.delta_seconds;
+        hull_buoyancy.buoyancy_ratio =
+            (hull_buoyancy.buoyancy_ratio - SINKING_RATE * clock.delta_seconds).max(0.);
+
+        if sinking.elapsed_seconds >= SINKING_SECONDS {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,115 @@
+mod resources;
+mod systems;
+
+use crate::args::run_conditions::desync_report_mode;
+use crate::desync_report::resources::{
+    hash_value, DesyncReportChecksums, DesyncReportHistory, FrameReport,
+};
+use crate::desync_report::systems::{record_frame_report, write_desync_report};
+use crate::stats::events::DesyncDetected;
+use bevy::prelude::*;
+use bevy_ggrs::{GgrsApp, SaveWorld, SaveWorldSet};
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+
+// Frames kept around, needs to cover the delay between saving a frame and the desync detection
+// of that frame arriving from a remote peer
+pub const DESYNC_REPORT_HISTORY_FRAMES: usize = 128;
+
+// Records the hashes of every checksummed resource and of every checksummed component on every
+// rollback entity, so that a desync can be traced back to what diverged first
+pub struct DesyncReportPlugin;
+
+impl Plugin for DesyncReportPlugin {
+    fn build(&self, app: &mut App) {
+        // Filled in by the checksum registrations of the simulation plugins
+        app.init_resource::<DesyncReportChecksums>();
+        app.init_resource::<DesyncReportHistory>();
+
+        // The combined checksum of the frame is available once the checksum set has run
+        app.add_systems(
+            SaveWorld,
+            record_frame_report
+                .in_set(SaveWorldSet::Snapshot)
+                .run_if(desync_report_mode),
+        );
+
+        app.add_systems(
+            Update,
+            write_desync_report.run_if(on_event::<DesyncDetected>().and_then(desync_report_mode)),
+        );
+    }
+}
+
+// Registers a rollback checksum and lists it for desync reports in the same call, so that reports
+// cover exactly what goes into the checksum
+pub trait ChecksumReportApp {
+    fn checksum_and_report_component<T: Component>(&mut self, checksum: fn(&T) -> u64)
+        -> &mut Self;
+
+    fn checksum_and_report_component_with_hash<T: Component + Hash>(&mut self) -> &mut Self;
+
+    fn checksum_and_report_resource<T: Resource>(&mut self, checksum: fn(&T) -> u64) -> &mut Self;
+}
+
+impl ChecksumReportApp for App {
+    fn checksum_and_report_component<T: Component>(
+        &mut self,
+        checksum: fn(&T) -> u64,
+    ) -> &mut Self {
+        self.checksum_component::<T>(checksum);
+        self.world
+            .get_resource_or_insert_with(DesyncReportChecksums::default)
+            .add_component::<T>(checksum);
+
+        self
+    }
+
+    fn checksum_and_report_component_with_hash<T: Component + Hash>(&mut self) -> &mut Self {
+        self.checksum_component_with_hash::<T>();
+        self.world
+            .get_resource_or_insert_with(DesyncReportChecksums::default)
+            .add_component::<T>(hash_value::<T>);
+
+        self
+    }
+
+    fn checksum_and_report_resource<T: Resource>(&mut self, checksum: fn(&T) -> u64) -> &mut Self {
+        self.checksum_resource::<T>(checksum);
+        self.world
+            .get_resource_or_insert_with(DesyncReportChecksums::default)
+            .add_resource::<T>(checksum);
+
+        self
+    }
+}
+
+// Prints the first differing component between two reports of the same frame
+pub fn diff(local_report_path: &Path, remote_report_path: &Path) -> Result<(), String> {
+    let local_report = read_frame_report(local_report_path)?;
+    let remote_report = read_frame_report(remote_report_path)?;
+
+    if local_report.frame != remote_report.frame {
+        println!(
+            "Reports are for different frames: {} and {}",
+            local_report.frame, remote_report.frame
+        );
+        return Ok(());
+    }
+
+    match local_report.first_difference(&remote_report) {
+        Some(difference) => println!("Frame {}: {difference}", local_report.frame),
+        None => println!("Frame {}: no differing component found", local_report.frame),
+    }
+
+    Ok(())
+}
+
+fn read_frame_report(path: &Path) -> Result<FrameReport, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+
+    serde_json::from_str(&json)
+        .map_err(|error| format!("Failed to parse {}: {error}", path.display()))
+}
@@ -0,0 +1,193 @@
+use crate::desync_report::DESYNC_REPORT_HISTORY_FRAMES;
+use bevy::prelude::*;
+use bevy::utils::{get_short_name, FixedState};
+use serde::{Deserialize, Serialize};
+use std::any::type_name;
+use std::collections::VecDeque;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+type ComponentHash = Box<dyn Fn(&EntityRef) -> Option<u64> + Send + Sync>;
+type ResourceHash = Box<dyn Fn(&World) -> Option<u64> + Send + Sync>;
+
+// Named hash functions of the components and resources written into desync reports, in report
+// order. Filled in by the same calls that register the rollback checksums.
+#[derive(Resource, Default)]
+pub struct DesyncReportChecksums {
+    pub components: Vec<(String, ComponentHash)>,
+    pub resources: Vec<(String, ResourceHash)>,
+}
+
+impl DesyncReportChecksums {
+    pub fn add_component<T: Component>(&mut self, checksum: fn(&T) -> u64) {
+        self.components.push((
+            get_short_name(type_name::<T>()),
+            Box::new(move |entity| entity.get::<T>().map(checksum)),
+        ));
+    }
+
+    pub fn add_resource<T: Resource>(&mut self, checksum: fn(&T) -> u64) {
+        self.resources.push((
+            get_short_name(type_name::<T>()),
+            Box::new(move |world| world.get_resource::<T>().map(checksum)),
+        ));
+    }
+}
+
+pub fn hash_value<T: Hash>(value: &T) -> u64 {
+    let mut hasher = FixedState.build_hasher();
+    value.hash(&mut hasher);
+
+    #[allow(clippy::manual_hash_one)]
+    hasher.finish()
+}
+
+#[derive(Resource, Default)]
+pub struct DesyncReportHistory(pub VecDeque<FrameReport>);
+
+impl DesyncReportHistory {
+    pub fn record(&mut self, frame_report: FrameReport) {
+        // Frames resimulated after a rollback replace the mispredicted ones
+        self.0
+            .retain(|recorded_report| recorded_report.frame < frame_report.frame);
+        self.0.push_back(frame_report);
+
+        while self.0.len() > DESYNC_REPORT_HISTORY_FRAMES {
+            self.0.pop_front();
+        }
+    }
+
+    pub fn get(&self, frame: i32) -> Option<&FrameReport> {
+        self.0
+            .iter()
+            .find(|frame_report| frame_report.frame == frame)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FrameReport {
+    pub frame: i32,
+    // Hexadecimal, to match the checksums logged on desync
+    pub checksum: String,
+    #[serde(default)]
+    pub resources: Vec<ComponentReport>,
+    pub entities: Vec<EntityReport>,
+}
+
+impl FrameReport {
+    pub fn first_difference(&self, other: &FrameReport) -> Option<String> {
+        for resource in &self.resources {
+            let other_hash = other
+                .resources
+                .iter()
+                .find(|other_resource| other_resource.name == resource.name)
+                .map(|other_resource| other_resource.hash);
+
+            if other_hash != Some(resource.hash) {
+                return Some(format!("resource {} differs", resource.name));
+            }
+        }
+
+        for other_resource in &other.resources {
+            if !self
+                .resources
+                .iter()
+                .any(|resource| resource.name == other_resource.name)
+            {
+                return Some(format!(
+                    "resource {} is missing on one of the peers",
+                    other_resource.name
+                ));
+            }
+        }
+
+        if self.resources.len() != other.resources.len() {
+            return Some("peers have a different number of checksummed resources".to_string());
+        }
+
+        for (entity, other_entity) in self.entities.iter().zip(&other.entities) {
+            if entity.rollback_order != other_entity.rollback_order {
+                return Some(format!(
+                    "rollback entity {} is missing on one of the peers",
+                    entity.rollback_order.min(other_entity.rollback_order)
+                ));
+            }
+
+            for component in &entity.components {
+                let other_hash = other_entity
+                    .components
+                    .iter()
+                    .find(|other_component| other_component.name == component.name)
+                    .map(|other_component| other_component.hash);
+
+                if other_hash != Some(component.hash) {
+                    return Some(format!(
+                        "{} of rollback entity {} ({}) differs",
+                        component.name, entity.rollback_order, entity.name
+                    ));
+                }
+            }
+
+            if entity.components.len() != other_entity.components.len() {
+                return Some(format!(
+                    "rollback entity {} ({}) has different components",
+                    entity.rollback_order, entity.name
+                ));
+            }
+        }
+
+        if self.entities.len() != other.entities.len() {
+            return Some("peers have a different number of rollback entities".to_string());
+        }
+
+        None
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EntityReport {
+    // Stable across peers, unlike entity ids
+    pub rollback_order: u64,
+    pub name: String,
+    pub components: Vec<ComponentReport>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ComponentReport {
+    pub name: String,
+    pub hash: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_report(resource_names: &[&str]) -> FrameReport {
+        FrameReport {
+            frame: 1,
+            checksum: "0".to_string(),
+            resources: resource_names
+                .iter()
+                .map(|name| ComponentReport {
+                    name: name.to_string(),
+                    hash: 0,
+                })
+                .collect(),
+            entities: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn resources_of_either_report_missing_from_the_other_differ() {
+        let local_report = frame_report(&["Wave"]);
+        let remote_report = frame_report(&["Wave", "Wind"]);
+
+        assert!(local_report.first_difference(&local_report).is_none());
+        assert!(local_report.first_difference(&remote_report).is_some());
+        assert!(remote_report.first_difference(&local_report).is_some());
+
+        // The same resource listed twice on one side
+        assert!(local_report
+            .first_difference(&frame_report(&["Wave", "Wave"]))
+            .is_some());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::desync_report::resources::{
+    ComponentReport, DesyncReportChecksums, DesyncReportHistory, EntityReport, FrameReport,
+};
+use crate::stats::events::DesyncDetected;
+use bevy::prelude::*;
+use bevy_ggrs::{Checksum, LocalPlayers, Rollback, RollbackFrameCount, RollbackOrdered};
+use std::fs;
+
+pub fn record_frame_report(world: &mut World) {
+    let frame = world.resource::<RollbackFrameCount>().0;
+    let checksum = world.resource::<Checksum>().0;
+    let rollback_ordered = world.resource::<RollbackOrdered>();
+    let checksums = world.resource::<DesyncReportChecksums>();
+
+    let resources = checksums
+        .resources
+        .iter()
+        .filter_map(|(name, hash)| {
+            hash(world).map(|hash| ComponentReport {
+                name: name.clone(),
+                hash,
+            })
+        })
+        .collect();
+
+    let mut entities: Vec<EntityReport> = world
+        .iter_entities()
+        .filter_map(|entity| {
+            let rollback = entity.get::<Rollback>()?;
+
+            Some(EntityReport {
+                rollback_order: rollback_ordered.order(*rollback),
+                name: entity
+                    .get::<Name>()
+                    .map(|name| name.to_string())
+                    .unwrap_or_default(),
+                components: checksums
+                    .components
+                    .iter()
+                    .filter_map(|(name, hash)| {
+                        hash(&entity).map(|hash| ComponentReport {
+                            name: name.clone(),
+                            hash,
+                        })
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+
+    entities.sort_by_key(|entity| entity.rollback_order);
+
+    world
+        .resource_mut::<DesyncReportHistory>()
+        .record(FrameReport {
+            frame,
+            checksum: format!("{checksum:X}"),
+            resources,
+            entities,
+        });
+}
+
+pub fn write_desync_report(
+    mut desync_event_reader: EventReader<DesyncDetected>,
+    history: Res<DesyncReportHistory>,
+    local_players: Res<LocalPlayers>,
+) {
+    for desync in desync_event_reader.read() {
+        let Some(frame_report) = history.get(desync.frame) else {
+            warn!(
+                "No desync report recorded for frame {}, it is older than the report history",
+                desync.frame
+            );
+            continue;
+        };
+
+        let handles: Vec<String> = local_players
+            .0
+            .iter()
+            .map(|handle| handle.to_string())
+            .collect();
+        let path = format!(
+            "desync_frame_{}_player_{}.json",
+            desync.frame,
+            handles.join("_")
+        );
+
+        let json =
+            serde_json::to_string_pretty(frame_report).expect("failed to serialize desync report");
+
+        match fs::write(&path, json) {
+            Ok(()) => info!(
+                "Wrote desync report to {path}. Local checksum: {:X}, remote checksum: {:X}",
+                desync.local_checksum, desync.remote_checksum
+            ),
+            Err(error) => error!("Failed to write desync report to {path}: {error}"),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+pub mod components;
+mod resources;
+mod systems;
+
+use crate::desync_report::ChecksumReportApp;
+use crate::disconnect::components::Disconnected;
+use crate::disconnect::resources::NotifiedDisconnects;
+use crate::disconnect::systems::{
+    despawn_expired_disconnect_notices, end_match_when_alone, mark_disconnected_players,
+    notify_disconnected_players, reset_disconnect_notices,
+};
+use crate::game_state::states::GameState;
+use bevy::prelude::*;
+use bevy_ggrs::{GgrsApp, GgrsSchedule};
+
+pub const DISCONNECT_NOTICE_SECONDS: f32 = 5.;
+
+// Ships of disconnected players are taken out of the match by sinking them. Inputs of a
+// disconnected player are marked as such on the same frame for all peers, which keeps this
+// deterministic.
+pub struct DisconnectPlugin;
+
+impl Plugin for DisconnectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(GgrsSchedule, mark_disconnected_players);
+
+        app.rollback_component_with_copy::<Disconnected>();
+
+        app.checksum_and_report_component_with_hash::<Disconnected>();
+    }
+}
+
+// Tells the local player about disconnected peers, and ends the match once nobody else is left
+pub struct DisconnectNoticePlugin;
+
+impl Plugin for DisconnectNoticePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NotifiedDisconnects>();
+
+        app.add_systems(
+            Update,
+            (
+                notify_disconnected_players,
+                despawn_expired_disconnect_notices,
+                end_match_when_alone,
+            )
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        app.add_systems(OnExit(GameState::InGame), reset_disconnect_notices);
+    }
+}
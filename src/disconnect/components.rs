@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+// Ship of a player that left the match
+#[derive(Component, Clone, Copy, Default, Hash)]
+pub struct Disconnected;
+
+#[derive(Component)]
+pub struct DisconnectNotice(pub Timer);
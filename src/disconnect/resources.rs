@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+// Player handles that the local player has already been told about, since a rollback might mark
+// the same ship as disconnected more than once
+#[derive(Resource, Default)]
+pub struct NotifiedDisconnects(pub HashSet<usize>);
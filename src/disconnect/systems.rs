@@ -0,0 +1,111 @@
+use crate::camera::resources::MainCamera;
+use crate::connection::systems::RollbackConfig;
+use crate::damage::components::Sinking;
+use crate::disconnect::components::{DisconnectNotice, Disconnected};
+use crate::disconnect::resources::NotifiedDisconnects;
+use crate::disconnect::DISCONNECT_NOTICE_SECONDS;
+use crate::game_state::states::GameState;
+use crate::player::components::Player;
+use bevy::prelude::*;
+use bevy_ggrs::ggrs::InputStatus;
+use bevy_ggrs::{LocalPlayers, PlayerInputs, Rollback, Session};
+
+pub fn mark_disconnected_players(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Player, Has<Sinking>), (With<Rollback>, Without<Disconnected>)>,
+    inputs: Res<PlayerInputs<RollbackConfig>>,
+) {
+    for (entity, player, is_sinking) in &player_query {
+        if inputs[player.handle].1 == InputStatus::Disconnected {
+            commands.entity(entity).insert(Disconnected);
+
+            if !is_sinking {
+                commands.entity(entity).insert(Sinking::default());
+            }
+        }
+    }
+}
+
+pub fn notify_disconnected_players(
+    mut commands: Commands,
+    player_query: Query<&Player, Added<Disconnected>>,
+    main_camera: Res<MainCamera>,
+    mut notified_disconnects: ResMut<NotifiedDisconnects>,
+) {
+    for player in &player_query {
+        if !notified_disconnects.0.insert(player.handle) {
+            continue;
+        }
+
+        warn!("Player {} disconnected", player.handle);
+
+        commands.spawn((
+            // Seems to be required in dev builds since using editor plugin results in multiple
+            // cameras
+            TargetCamera(main_camera.id),
+            TextBundle::from_section(
+                format!("Player {} disconnected", player.handle + 1),
+                TextStyle {
+                    font_size: 24.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(48. + 32. * player.handle as f32),
+                left: Val::Px(16.),
+                ..default()
+            }),
+            DisconnectNotice(Timer::from_seconds(
+                DISCONNECT_NOTICE_SECONDS,
+                TimerMode::Once,
+            )),
+        ));
+    }
+}
+
+pub fn despawn_expired_disconnect_notices(
+    mut commands: Commands,
+    mut notice_query: Query<(Entity, &mut DisconnectNotice)>,
+    time: Res<Time>,
+) {
+    for (entity, mut notice) in &mut notice_query {
+        if notice.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn end_match_when_alone(
+    player_query: Query<&Player, Without<Disconnected>>,
+    local_players: Res<LocalPlayers>,
+    session: Option<Res<Session<RollbackConfig>>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Local sessions have nobody to lose
+    if matches!(session.as_deref(), None | Some(Session::SyncTest(_))) {
+        return;
+    }
+
+    let remote_players_left = player_query
+        .iter()
+        .any(|player| !local_players.0.contains(&player.handle));
+
+    if !remote_players_left {
+        info!("No other players left, ending match");
+        next_state.set(GameState::PostMatch);
+    }
+}
+
+pub fn reset_disconnect_notices(
+    mut commands: Commands,
+    notice_query: Query<Entity, With<DisconnectNotice>>,
+    mut notified_disconnects: ResMut<NotifiedDisconnects>,
+) {
+    notified_disconnects.0.clear();
+
+    for entity in &notice_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,8 +1,9 @@
+pub mod components;
 pub mod resources;
 mod systems;
 
 use crate::focal_point::resources::FocalPoint;
-use crate::focal_point::systems::update_focal_point;
+use crate::focal_point::systems::{update_camera_focal_points, update_focal_point};
 use crate::game_state::states::GameState;
 use bevy::prelude::*;
 
@@ -14,7 +15,9 @@ impl Plugin for FocalPointPlugin {
 
         app.add_systems(
             Update,
-            update_focal_point.run_if(in_state(GameState::InGame)),
+            (update_camera_focal_points, update_focal_point)
+                .chain()
+                .run_if(in_state(GameState::InGame)),
         );
     }
 }
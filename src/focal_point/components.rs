@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+// Ship that the camera this is attached to follows
+#[derive(Component, Default)]
+pub struct CameraFocalPoint {
+    pub handle: usize,
+    pub translation: Vec3,
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
 
+// Center of the focal points of all cameras, around which the ocean and sky are placed
 #[derive(Resource)]
 pub struct FocalPoint(pub Vec3);
@@ -1,20 +1,34 @@
+use crate::focal_point::components::CameraFocalPoint;
 use crate::focal_point::resources::FocalPoint;
 use crate::player::components::Player;
 use bevy::prelude::*;
-use bevy_ggrs::LocalPlayers;
 
-pub fn update_focal_point(
+pub fn update_camera_focal_points(
     player_query: Query<(&Player, &Transform)>,
-    local_players: Res<LocalPlayers>,
-    mut focal_point: ResMut<FocalPoint>,
+    mut camera_focal_point_query: Query<&mut CameraFocalPoint>,
 ) {
-    for (player, transform) in &player_query {
-        // Ignore non-local players
-        if !local_players.0.contains(&player.handle) {
-            continue;
+    for mut camera_focal_point in &mut camera_focal_point_query {
+        for (player, transform) in &player_query {
+            if player.handle != camera_focal_point.handle {
+                continue;
+            }
+
+            camera_focal_point.translation = transform.translation;
+            camera_focal_point.translation.y = 0.;
         }
+    }
+}
+
+pub fn update_focal_point(
+    camera_focal_point_query: Query<&CameraFocalPoint>,
+    mut focal_point: ResMut<FocalPoint>,
+) {
+    let translations: Vec<Vec3> = camera_focal_point_query
+        .iter()
+        .map(|camera_focal_point| camera_focal_point.translation)
+        .collect();
 
-        focal_point.0 = transform.translation;
-        focal_point.0.y = 0.;
+    if !translations.is_empty() {
+        focal_point.0 = translations.iter().sum::<Vec3>() / translations.len() as f32;
     }
 }
@@ -1 +1,2 @@
+pub mod error_screen;
 pub mod states;
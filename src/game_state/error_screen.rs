@@ -0,0 +1,32 @@
+use crate::camera::resources::MainCamera;
+use bevy::prelude::*;
+
+// Shows why the game can't go on, for the states it fails into
+pub fn spawn_error_screen(commands: &mut Commands, main_camera: &MainCamera, message: &str) {
+    commands
+        .spawn((
+            // Seems to be required in dev builds since using editor plugin results in multiple
+            // cameras
+            TargetCamera(main_camera.id),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|child_builder| {
+            child_builder.spawn(TextBundle::from_section(
+                message,
+                TextStyle {
+                    font_size: 24.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
@@ -4,7 +4,12 @@ use bevy::prelude::*;
 pub enum GameState {
     #[default]
     LoadingAssets,
+    LoadingAssetsFailed,
     SplashScreen,
+    KeyBindings,
     Matchmaking,
+    Lobby,
     InGame,
+    PostMatch,
+    ConnectionFailed,
 }
@@ -0,0 +1,361 @@
+use crate::args::resources::Args;
+use crate::assets::resources::ModelAssets;
+use crate::assets::MODEL_FILE_NAMES;
+use crate::connection::systems::RollbackConfig;
+use crate::connection::FPS;
+use crate::game_state::states::GameState;
+use crate::ocean::resources::Wave;
+use crate::physics::components::LinearVelocity;
+use crate::player::components::Player;
+use crate::player::definitions::Shipyard;
+use crate::player::resources::ShipClasses;
+use crate::replay::resources::{Replay, ScriptedInputs};
+use crate::replay::systems::read_scripted_inputs;
+use crate::rollback_audit::RollbackAuditPlugin;
+use crate::simulation::SimulationPlugins;
+use crate::utils::hash::{hash_quat, hash_vec3};
+use crate::wind::resources::WindConditions;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::FixedState;
+use bevy_ggrs::{ggrs, GgrsApp, GgrsPlugin, ReadInputs, RollbackFrameCount, Session};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::time::Duration;
+
+// Each update is fed one frame worth of time, but the first updates may not accumulate enough to
+// advance a frame
+const MAX_UPDATES_PER_FRAME: usize = 4;
+
+pub struct ShipState {
+    pub handle: usize,
+    pub transform: Transform,
+    pub linear_velocity: LinearVelocity,
+}
+
+// Runs the rollback simulation without window or renderer, stepping one frame at a time from a
+// scripted input stream.
+pub struct HeadlessSimulation {
+    app: App,
+}
+
+impl HeadlessSimulation {
+    pub fn new(args: Args, scripted_inputs: ScriptedInputs, ship_classes: ShipClasses) -> Self {
+        let mut app = App::new();
+
+        app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+
+        // Feed exactly one rollback frame worth of time per update
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1. / FPS as f64,
+        )));
+
+        app.add_plugins(GgrsPlugin::<RollbackConfig>::default());
+        app.set_rollback_schedule_fps(FPS);
+        app.add_plugins(SimulationPlugins);
+
+        #[cfg(debug_assertions)]
+        app.add_plugins(RollbackAuditPlugin);
+
+        // Scenes are never spawned without a renderer, so default handles are good enough
+        app.insert_resource(ModelAssets {
+            scene_handles: MODEL_FILE_NAMES
+                .into_iter()
+                .map(|name| (name, Handle::default()))
+                .collect(),
+        });
+        app.insert_resource(Shipyard::read());
+        app.insert_resource(ship_classes);
+
+        app.insert_resource(scripted_inputs);
+        app.add_systems(ReadInputs, read_scripted_inputs);
+
+        let mut session_builder = ggrs::SessionBuilder::<RollbackConfig>::new()
+            .with_num_players(args.num_players)
+            .with_check_distance(args.check_distance);
+
+        for handle in 0..args.num_players {
+            session_builder = session_builder
+                .add_player(ggrs::PlayerType::Local, handle)
+                .expect("failed to add player");
+        }
+
+        let ggrs_session = session_builder
+            .start_synctest_session()
+            .expect("failed to start session");
+
+        app.insert_resource(Session::SyncTest(ggrs_session));
+        app.insert_resource(args);
+        app.insert_state(GameState::InGame);
+
+        // Run startup and spawn players before the first frame is simulated
+        app.update();
+
+        Self { app }
+    }
+
+    // Plays back the recorded inputs in the environment the replay was recorded in
+    pub fn from_replay(args: Args, replay: &Replay) -> Self {
+        let args = Args {
+            num_players: replay.args.num_players,
+            hull: replay.args.hull.clone(),
+            wind_driven_waves: replay.args.wind_driven_waves,
+            ..args
+        };
+
+        let mut simulation = Self::new(
+            args,
+            replay.scripted_inputs(),
+            ShipClasses(replay.ship_classes.clone()),
+        );
+
+        let world = &mut simulation.app.world;
+        world.resource_scope(|world, mut wave: Mut<Wave>| {
+            replay
+                .environment
+                .apply(&mut wave, &mut world.resource_mut::<WindConditions>());
+        });
+
+        simulation
+    }
+
+    pub fn frame(&self) -> i32 {
+        self.app.world.resource::<RollbackFrameCount>().0
+    }
+
+    // Advances the simulation by exactly one rollback frame. Panics if the session refuses to
+    // advance, which a sync test session does when checksums of resimulated frames mismatch.
+    pub fn step(&mut self) {
+        let frame = self.frame();
+        for _ in 0..MAX_UPDATES_PER_FRAME {
+            self.app.update();
+            if self.frame() != frame {
+                return;
+            }
+        }
+
+        panic!("Simulation did not advance past frame {frame}");
+    }
+
+    pub fn run(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.step();
+        }
+    }
+
+    pub fn ship_states(&mut self) -> Vec<ShipState> {
+        let mut ship_states: Vec<ShipState> = self
+            .app
+            .world
+            .query::<(&Player, &Transform, &LinearVelocity)>()
+            .iter(&self.app.world)
+            .map(|(player, transform, linear_velocity)| ShipState {
+                handle: player.handle,
+                transform: *transform,
+                linear_velocity: *linear_velocity,
+            })
+            .collect();
+
+        ship_states.sort_by_key(|ship_state| ship_state.handle);
+
+        ship_states
+    }
+
+    // Combined hash of all ship transforms, comparable across runs for golden file regressions
+    pub fn ship_transforms_hash(&mut self) -> u64 {
+        let mut hasher = FixedState.build_hasher();
+
+        for ship_state in self.ship_states() {
+            hash_vec3(ship_state.transform.translation).hash(&mut hasher);
+            hash_quat(ship_state.transform.rotation).hash(&mut hasher);
+        }
+
+        #[allow(clippy::manual_hash_one)]
+        hasher.finish()
+    }
+}
+
+pub fn run(args: Args, frames: usize) -> Result<(), String> {
+    let mut simulation = match &args.replay {
+        Some(path) => HeadlessSimulation::from_replay(args.clone(), &Replay::load(path)?),
+        None => HeadlessSimulation::new(args, ScriptedInputs::default(), ShipClasses::default()),
+    };
+    simulation.run(frames);
+
+    for ship_state in simulation.ship_states() {
+        println!(
+            "Frame {} player {}: translation {}, rotation {}, linear velocity {}",
+            simulation.frame(),
+            ship_state.handle,
+            ship_state.transform.translation,
+            ship_state.transform.rotation,
+            ship_state.linear_velocity.0,
+        );
+    }
+
+    println!(
+        "Frame {} ship transforms hash: {:X}",
+        simulation.frame(),
+        simulation.ship_transforms_hash()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::{PlayerInput, INPUT_FIRE, INPUT_LEFT, INPUT_RIGHT, INPUT_UP};
+    use crate::physics::components::Rudder;
+    use bevy::ecs::system::SystemState;
+    use clap::Parser;
+
+    const FRAMES: usize = 30;
+
+    // Rotation of the rudder of each ship, ordered by player handle
+    fn rudder_rotations(simulation: &mut HeadlessSimulation) -> Vec<Quat> {
+        let mut system_state: SystemState<(
+            Query<(Entity, &Player)>,
+            Query<&Children>,
+            Query<&Transform, With<Rudder>>,
+        )> = SystemState::new(&mut simulation.app.world);
+        let (player_query, children_query, rudder_query) = system_state.get(&simulation.app.world);
+
+        let mut rudder_rotations: Vec<_> = player_query
+            .iter()
+            .flat_map(|(ship_entity, player)| {
+                children_query
+                    .iter_descendants(ship_entity)
+                    .filter_map(|descendant| rudder_query.get(descendant).ok())
+                    .map(move |transform| (player.handle, transform.rotation))
+            })
+            .collect();
+        rudder_rotations.sort_by_key(|(handle, _)| *handle);
+
+        rudder_rotations
+            .into_iter()
+            .map(|(_, rotation)| rotation)
+            .collect()
+    }
+
+    #[test]
+    fn steering_one_ship_leaves_the_rudder_of_the_other_untouched() {
+        let args = Args::parse_from(["pirate-sea-jam", "--num-players", "2"]);
+        let steering_input = PlayerInput {
+            buttons: INPUT_RIGHT,
+            ..default()
+        };
+        let scripted_inputs = ScriptedInputs {
+            inputs: vec![
+                vec![steering_input; FRAMES],
+                vec![PlayerInput::default(); FRAMES],
+            ],
+            ..default()
+        };
+
+        let mut simulation = HeadlessSimulation::new(args, scripted_inputs, ShipClasses::default());
+        simulation.run(FRAMES);
+
+        let rudder_rotations = rudder_rotations(&mut simulation);
+        assert_eq!(rudder_rotations.len(), 2);
+        assert_ne!(rudder_rotations[0], Quat::IDENTITY);
+        assert_eq!(rudder_rotations[1], Quat::IDENTITY);
+    }
+
+    // Sails, turns and fires on and off, so that most of the simulation gets exercised
+    fn varied_inputs(frames: usize, seed: usize) -> Vec<PlayerInput> {
+        (0..frames)
+            .map(|frame| {
+                let phase = (frame + seed * 7) / 10;
+                let mut buttons = INPUT_UP;
+                buttons |= [0, INPUT_LEFT, INPUT_RIGHT][phase % 3];
+                if phase % 4 == 1 {
+                    buttons |= INPUT_FIRE;
+                }
+                PlayerInput {
+                    buttons,
+                    ..default()
+                }
+            })
+            .collect()
+    }
+
+    fn run_to_hash(inputs: Vec<Vec<PlayerInput>>) -> u64 {
+        let frames = inputs[0].len();
+        let args = Args::parse_from([
+            "pirate-sea-jam",
+            "--num-players",
+            "2",
+            "--check-distance",
+            "2",
+        ]);
+        let scripted_inputs = ScriptedInputs {
+            inputs,
+            ..default()
+        };
+
+        let mut simulation = HeadlessSimulation::new(args, scripted_inputs, ShipClasses::default());
+        simulation.run(frames);
+
+        simulation.ship_transforms_hash()
+    }
+
+    // The sync test session already compares checksums while resimulating, this also catches state
+    // that leaks in from outside of the simulation, like iteration order or wall clock time
+    #[test]
+    fn same_inputs_sail_the_ships_to_the_same_state() {
+        let frames = 4 * FRAMES;
+        let inputs = vec![varied_inputs(frames, 0), varied_inputs(frames, 1)];
+        let hash = run_to_hash(inputs.clone());
+
+        assert_eq!(hash, run_to_hash(inputs));
+        // Otherwise the inputs did not get through to the ships
+        assert_ne!(
+            hash,
+            run_to_hash(vec![vec![PlayerInput::default(); frames]; 2])
+        );
+    }
+
+    // Recoil is applied all at once, so too much of it sets light hulls spinning faster than the
+    // drag of the water can be integrated
+    #[test]
+    fn rafts_firing_broadsides_keep_a_finite_state() {
+        let frames = 4 * FRAMES;
+        let args = Args::parse_from(["pirate-sea-jam", "--num-players", "2", "--hull", "Raft"]);
+        let scripted_inputs = ScriptedInputs {
+            inputs: vec![varied_inputs(frames, 0), varied_inputs(frames, 1)],
+            ..default()
+        };
+
+        let mut simulation = HeadlessSimulation::new(args, scripted_inputs, ShipClasses::default());
+        simulation.run(frames);
+
+        for ship_state in simulation.ship_states() {
+            assert!(ship_state.transform.translation.is_finite());
+            assert!(ship_state.transform.rotation.is_finite());
+        }
+    }
+
+    // Recorded with a Medium and a Raft sailing, turning and firing. Update the hash when a change
+    // to the simulation is meant to change where the ships end up.
+    const GOLDEN_REPLAY_FILE_NAME: &str = "medium_and_raft.replay.json";
+    const GOLDEN_SHIP_TRANSFORMS_HASH: u64 = 0x7731C561C1D92C65;
+
+    #[test]
+    fn golden_replay_sails_the_ships_to_the_recorded_state() {
+        let path = bevy::asset::io::file::FileAssetReader::get_base_path()
+            .join("tests/fixtures")
+            .join(GOLDEN_REPLAY_FILE_NAME);
+        let replay = Replay::load(&path).unwrap();
+
+        let mut simulation =
+            HeadlessSimulation::from_replay(Args::parse_from(["pirate-sea-jam"]), &replay);
+        simulation.run(replay.frame_count());
+
+        assert_eq!(
+            simulation.ship_transforms_hash(),
+            GOLDEN_SHIP_TRANSFORMS_HASH,
+            "ship transforms hash changed to {:X}",
+            simulation.ship_transforms_hash()
+        );
+    }
+}
@@ -1,45 +1,138 @@
-use crate::inputs::systems::{read_local_inputs, read_mouse_input};
+use crate::args::run_conditions::replay_mode;
+use crate::game_state::states::GameState;
+use crate::inputs::resources::{InputBindings, SelectedBroadsides};
+use crate::inputs::systems::{
+    load_input_bindings, read_gamepad_orbit_input, read_local_inputs, read_mouse_input,
+    select_broadsides,
+};
 use bevy::prelude::*;
 use bevy_ggrs::ggrs::InputStatus;
-use bevy_ggrs::ReadInputs;
+use bevy_ggrs::{LocalPlayers, ReadInputs};
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
 
-mod systems;
+pub mod resources;
+pub mod systems;
 
-// pub const INPUT_UP: u8 = 1 << 0;
-// pub const INPUT_DOWN: u8 = 1 << 1;
+pub const INPUT_UP: u8 = 1 << 0;
+pub const INPUT_DOWN: u8 = 1 << 1;
 pub const INPUT_LEFT: u8 = 1 << 2;
 pub const INPUT_RIGHT: u8 = 1 << 3;
 pub const INPUT_FIRE: u8 = 1 << 4;
+// Selects which side of the ship fires, port when unset
+pub const INPUT_STARBOARD: u8 = 1 << 5;
 
-pub fn turn_action_from_input(input_and_status: (u8, InputStatus)) -> i32 {
-    let input = match input_and_status.1 {
+pub const INPUT_BINDINGS_FILE_NAME: &str = "input.bindings.ron";
+
+// Full deflection of the quantized steering axis
+pub const STEERING_AXIS_MAX: i8 = 127;
+
+// Camera orbit speed at full right stick deflection, in the pixels per second the mouse would move
+pub const GAMEPAD_ORBIT_SPEED: f32 = 600.;
+
+// Input sent to peers each frame. Analog values are quantized to integers so that every peer
+// steers by exactly the same amount.
+#[repr(C)]
+#[derive(
+    Clone, Copy, PartialEq, Eq, Default, Debug, Hash, Pod, Zeroable, Serialize, Deserialize,
+)]
+pub struct PlayerInput {
+    pub buttons: u8,
+    // From -STEERING_AXIS_MAX at full port to STEERING_AXIS_MAX at full starboard
+    pub steering: i8,
+}
+
+impl PlayerInput {
+    pub fn quantize_steering(axis: f32) -> i8 {
+        (axis.clamp(-1., 1.) * STEERING_AXIS_MAX as f32).round() as i8
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Hash, Deserialize)]
+pub enum Broadside {
+    #[default]
+    Port,
+    Starboard,
+}
+
+fn input_from_status(input_and_status: (PlayerInput, InputStatus)) -> PlayerInput {
+    match input_and_status.1 {
         InputStatus::Confirmed => input_and_status.0,
         InputStatus::Predicted => input_and_status.0,
-        InputStatus::Disconnected => 0, // disconnected players do nothing
-    };
+        InputStatus::Disconnected => PlayerInput::default(), // disconnected players do nothing
+    }
+}
+
+// Turning in steps of the quantized steering axis, where the turn buttons are full deflection
+pub fn turn_action_from_input(input_and_status: (PlayerInput, InputStatus)) -> i32 {
+    let input = input_from_status(input_and_status);
 
-    let mut turn: i32 = 0;
+    let mut turn = input.steering as i32;
 
-    if input & INPUT_RIGHT != 0 {
-        turn += 1;
+    if input.buttons & INPUT_RIGHT != 0 {
+        turn += STEERING_AXIS_MAX as i32;
     }
-    if input & INPUT_LEFT != 0 {
-        turn -= 1;
+    if input.buttons & INPUT_LEFT != 0 {
+        turn -= STEERING_AXIS_MAX as i32;
     }
 
-    turn
+    turn.clamp(-(STEERING_AXIS_MAX as i32), STEERING_AXIS_MAX as i32)
 }
 
-pub fn fire(input: u8) -> bool {
-    input & INPUT_FIRE != 0
+// Letting out sail is positive, reefing negative
+pub fn accelerate_action_from_input(input_and_status: (PlayerInput, InputStatus)) -> i32 {
+    let input = input_from_status(input_and_status);
+
+    let mut accelerate: i32 = 0;
+
+    if input.buttons & INPUT_UP != 0 {
+        accelerate += 1;
+    }
+    if input.buttons & INPUT_DOWN != 0 {
+        accelerate -= 1;
+    }
+
+    accelerate
+}
+
+pub fn fire(input: PlayerInput) -> bool {
+    input.buttons & INPUT_FIRE != 0
+}
+
+pub fn broadside(input: PlayerInput) -> Broadside {
+    if input.buttons & INPUT_STARBOARD != 0 {
+        Broadside::Starboard
+    } else {
+        Broadside::Port
+    }
 }
 
 pub struct InputsPlugin;
 
 impl Plugin for InputsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(ReadInputs, read_local_inputs);
+        app.add_systems(OnExit(GameState::LoadingAssets), load_input_bindings);
+
+        app.init_resource::<SelectedBroadsides>();
+
+        // Key bindings are only known once the assets are loaded, which sessions start after
+        app.add_systems(
+            ReadInputs,
+            read_local_inputs.run_if(resource_exists::<InputBindings>.and_then(not(replay_mode))),
+        );
+        app.add_systems(
+            Update,
+            select_broadsides.run_if(
+                resource_exists::<InputBindings>
+                    .and_then(resource_exists::<LocalPlayers>)
+                    .and_then(not(replay_mode)),
+            ),
+        );
 
         app.add_systems(Update, read_mouse_input);
+        app.add_systems(
+            Update,
+            read_gamepad_orbit_input.run_if(in_state(GameState::InGame)),
+        );
     }
 }
@@ -0,0 +1,367 @@
+use crate::inputs::{Broadside, INPUT_BINDINGS_FILE_NAME};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum InputAction {
+    TurnPort,
+    TurnStarboard,
+    LetOutSail,
+    ReefSail,
+    PortBroadside,
+    StarboardBroadside,
+    Fire,
+    EndMatch,
+}
+
+impl InputAction {
+    // Actions sailing and firing a ship, in the order they are listed in instructions and the key
+    // bindings menu
+    pub const SHIP: [InputAction; 7] = [
+        InputAction::TurnPort,
+        InputAction::TurnStarboard,
+        InputAction::LetOutSail,
+        InputAction::ReefSail,
+        InputAction::PortBroadside,
+        InputAction::StarboardBroadside,
+        InputAction::Fire,
+    ];
+
+    // In the order the actions are listed in the key bindings menu. Ending the match is not sent to
+    // peers, and is listed once for all players in instructions.
+    pub const ALL: [InputAction; 8] = [
+        InputAction::TurnPort,
+        InputAction::TurnStarboard,
+        InputAction::LetOutSail,
+        InputAction::ReefSail,
+        InputAction::PortBroadside,
+        InputAction::StarboardBroadside,
+        InputAction::Fire,
+        InputAction::EndMatch,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            InputAction::TurnPort => "turn port",
+            InputAction::TurnStarboard => "turn starboard",
+            InputAction::LetOutSail => "let out sail",
+            InputAction::ReefSail => "reef sail",
+            InputAction::PortBroadside => "port broadside",
+            InputAction::StarboardBroadside => "starboard broadside",
+            InputAction::Fire => "fire cannons",
+            InputAction::EndMatch => "end match",
+        }
+    }
+}
+
+// Keys of every action. The first key of an action is the one shown in instructions, and the one
+// replaced when rebinding.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyBindingSet {
+    pub keys: BTreeMap<InputAction, Vec<KeyCode>>,
+}
+
+impl KeyBindingSet {
+    pub fn keys(&self, action: InputAction) -> &[KeyCode] {
+        self.keys
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn pressed(&self, keys: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        keys.any_pressed(self.keys(action).iter().copied())
+    }
+
+    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        keys.any_just_pressed(self.keys(action).iter().copied())
+    }
+
+    pub fn key_label(&self, action: InputAction) -> String {
+        match self.keys(action).first() {
+            Some(key) => key_name(*key),
+            None => "unbound".to_string(),
+        }
+    }
+
+    pub fn control_keys_text(&self) -> String {
+        InputAction::SHIP
+            .iter()
+            .map(|action| format!("[{}] {}", self.key_label(*action), action.description()))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+// One set of keys for each player sharing the keyboard. A player playing alone can use the keys of
+// every set. The defaults are an asset, which the bindings a player saved replace.
+#[derive(Asset, Resource, TypePath, Clone, Serialize, Deserialize)]
+pub struct InputBindings {
+    pub players: Vec<KeyBindingSet>,
+}
+
+impl InputBindings {
+    // The sets of keys the local player at given index, out of given number of local players, plays
+    // with
+    pub fn sets(&self, local_player: usize, num_local_players: usize) -> &[KeyBindingSet] {
+        if num_local_players == 1 {
+            &self.players
+        } else {
+            self.players
+                .get(local_player..local_player + 1)
+                .unwrap_or_default()
+        }
+    }
+
+    // Label of the keys of the action of given player, empty if there is no such player
+    pub fn key_label(&self, player: usize, action: InputAction) -> String {
+        self.players
+            .get(player)
+            .map(|set| set.key_label(action))
+            .unwrap_or_default()
+    }
+
+    // Label of the keys of the action of the first player who has any, for actions every player
+    // shares
+    pub fn shared_key_label(&self, action: InputAction) -> String {
+        self.players
+            .iter()
+            .find(|set| !set.keys(action).is_empty())
+            .map(|set| set.key_label(action))
+            .unwrap_or_else(|| "unbound".to_string())
+    }
+
+    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        self.players
+            .iter()
+            .any(|set| set.just_pressed(keys, action))
+    }
+
+    // Makes the key the first key of the action of given player, taking it away from any other
+    // action so that a key never triggers two actions
+    pub fn rebind(&mut self, player: usize, action: InputAction, key: KeyCode) {
+        for set in &mut self.players {
+            for action_keys in set.keys.values_mut() {
+                action_keys.retain(|action_key| *action_key != key);
+            }
+        }
+
+        let Some(set) = self.players.get_mut(player) else {
+            return;
+        };
+
+        let action_keys = set.keys.entry(action).or_default();
+        match action_keys.first_mut() {
+            Some(first_key) => *first_key = key,
+            None => action_keys.push(key),
+        }
+    }
+
+    // Falls back to the default bindings when none have been saved yet, they can't be read, or they
+    // have fewer key sets than the given number of local players needs
+    pub fn load(defaults: &InputBindings, num_local_players: usize) -> Self {
+        let Some(ron) = read_stored_bindings() else {
+            return defaults.clone();
+        };
+
+        let bindings: Self = match ron::from_str(&ron) {
+            Ok(bindings) => bindings,
+            Err(error) => {
+                warn!(
+                    "Failed to parse {INPUT_BINDINGS_FILE_NAME}, using default key bindings: {error}"
+                );
+                return defaults.clone();
+            }
+        };
+
+        if !bindings.has_sets_for(defaults, num_local_players) {
+            warn!(
+                "{INPUT_BINDINGS_FILE_NAME} has {} key sets, using default key bindings",
+                bindings.players.len()
+            );
+            return defaults.clone();
+        }
+
+        bindings.with_missing_defaults(defaults)
+    }
+
+    // Actions added since the bindings were saved get their default keys, unless those keys were
+    // rebound to another action
+    fn with_missing_defaults(mut self, defaults: &InputBindings) -> Self {
+        for (player, default_set) in defaults.players.iter().enumerate() {
+            for (action, default_keys) in &default_set.keys {
+                let bound = |key: &KeyCode| {
+                    self.players.iter().any(|set| {
+                        set.keys
+                            .values()
+                            .flatten()
+                            .any(|bound_key| bound_key == key)
+                    })
+                };
+                let unbound_keys: Vec<KeyCode> = default_keys
+                    .iter()
+                    .copied()
+                    .filter(|key| !bound(key))
+                    .collect();
+
+                if let Some(set) = self.players.get_mut(player) {
+                    set.keys.entry(*action).or_insert(unbound_keys);
+                }
+            }
+        }
+
+        self
+    }
+
+    // Every local player needs a set of their own, as far as the defaults provide them
+    fn has_sets_for(&self, defaults: &InputBindings, num_local_players: usize) -> bool {
+        let num_needed_sets = num_local_players.min(defaults.players.len()).max(1);
+        self.players.len() >= num_needed_sets
+    }
+
+    pub fn save(&self) {
+        let ron = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .expect("failed to serialize key bindings");
+
+        if let Err(error) = write_stored_bindings(&ron) {
+            warn!("Failed to save {INPUT_BINDINGS_FILE_NAME}: {error}");
+        }
+    }
+}
+
+// Drops the prefixes of letter and digit keys, so that KeyA reads as A
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+// Rebound keys are saved in the user's config directory natively, and in local storage in the
+// browser, leaving the default bindings in the assets untouched
+
+#[cfg(not(target_arch = "wasm32"))]
+const CONFIG_DIRECTORY_NAME: &str = "pirate-sea-jam";
+
+#[cfg(not(target_arch = "wasm32"))]
+fn user_bindings_path() -> Option<std::path::PathBuf> {
+    use std::env::var_os;
+    use std::path::PathBuf;
+
+    let config_dir = if cfg!(target_os = "windows") {
+        var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    Some(
+        config_dir?
+            .join(CONFIG_DIRECTORY_NAME)
+            .join(INPUT_BINDINGS_FILE_NAME),
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_stored_bindings() -> Option<String> {
+    std::fs::read_to_string(user_bindings_path()?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_stored_bindings(ron: &str) -> Result<(), String> {
+    let path = user_bindings_path().ok_or("no config directory is known for this user")?;
+
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory).map_err(|error| error.to_string())?;
+    }
+
+    std::fs::write(path, ron).map_err(|error| error.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_stored_bindings() -> Option<String> {
+    local_storage()?.get_item(INPUT_BINDINGS_FILE_NAME).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_stored_bindings(ron: &str) -> Result<(), String> {
+    local_storage()
+        .ok_or("local storage is unavailable")?
+        .set_item(INPUT_BINDINGS_FILE_NAME, ron)
+        .map_err(|error| format!("{error:?}"))
+}
+
+// Broadside each local player selected, kept until the other side is chosen. Selected every render
+// frame, since the rollback schedule does not read inputs on frames that do not advance it and would
+// miss presses landing on them.
+#[derive(Resource, Default)]
+pub struct SelectedBroadsides(pub HashMap<usize, Broadside>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_bindings() -> InputBindings {
+        let path = bevy::asset::io::file::FileAssetReader::get_base_path()
+            .join("assets")
+            .join(INPUT_BINDINGS_FILE_NAME);
+        ron::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn bindings_without_a_set_for_every_local_player_are_rejected() {
+        let defaults = default_bindings();
+
+        let empty = InputBindings { players: vec![] };
+        assert!(!empty.has_sets_for(&defaults, 1));
+
+        let single = InputBindings {
+            players: defaults.players[..1].to_vec(),
+        };
+        assert!(single.has_sets_for(&defaults, 1));
+        assert!(!single.has_sets_for(&defaults, 2));
+
+        // Local players beyond the sets the defaults provide play with gamepads only
+        assert!(defaults.has_sets_for(&defaults, 4));
+    }
+
+    #[test]
+    fn actions_missing_from_saved_bindings_get_their_default_keys() {
+        let defaults = default_bindings();
+
+        let mut saved = defaults.clone();
+        for set in &mut saved.players {
+            set.keys.remove(&InputAction::EndMatch);
+        }
+        saved.rebind(0, InputAction::Fire, KeyCode::KeyF);
+
+        let bindings = saved.with_missing_defaults(&defaults);
+        assert_eq!(
+            bindings.players[0].keys(InputAction::EndMatch),
+            [KeyCode::F10]
+        );
+        assert_eq!(bindings.players[0].keys(InputAction::Fire), [KeyCode::KeyF]);
+
+        // A default key rebound to another action stays with that action
+        let mut saved = defaults.clone();
+        for set in &mut saved.players {
+            set.keys.remove(&InputAction::EndMatch);
+        }
+        saved.rebind(0, InputAction::Fire, KeyCode::F10);
+
+        let bindings = saved.with_missing_defaults(&defaults);
+        assert!(bindings.players[0].keys(InputAction::EndMatch).is_empty());
+        assert_eq!(bindings.players[0].keys(InputAction::Fire), [KeyCode::F10]);
+    }
+}
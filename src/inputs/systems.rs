@@ -1,43 +1,234 @@
+use crate::args::resources::Args;
+use crate::assets::resources::InputBindingsAsset;
 use crate::connection::systems::RollbackConfig;
-use crate::inputs::{INPUT_FIRE, INPUT_LEFT, INPUT_RIGHT};
+use crate::inputs::resources::{InputAction, InputBindings, SelectedBroadsides};
+use crate::inputs::{
+    Broadside, PlayerInput, GAMEPAD_ORBIT_SPEED, INPUT_DOWN, INPUT_FIRE, INPUT_LEFT, INPUT_RIGHT,
+    INPUT_STARBOARD, INPUT_UP,
+};
 use crate::orbiting_camera::events::OrbitMotion;
 use bevy::input::mouse::MouseMotion;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
 use bevy_ggrs::{LocalInputs, LocalPlayers};
 
+// Once the default bindings are loaded, replaced by the bindings the player saved if there are any
+pub fn load_input_bindings(
+    mut commands: Commands,
+    args: Res<Args>,
+    input_bindings_asset: Res<InputBindingsAsset>,
+    input_bindings: Res<Assets<InputBindings>>,
+) {
+    let Some(defaults) = input_bindings.get(input_bindings_asset.handle.id()) else {
+        return;
+    };
+
+    // Every player of a local session plays on this machine, each with their own keys
+    let num_local_players = if args.sync_test { args.num_players } else { 1 };
+    commands.insert_resource(InputBindings::load(defaults, num_local_players));
+}
+
+// Gamepads in the order they were connected, so that the first one belongs to the first local player
+fn connected_gamepads(gamepads: &Gamepads) -> Vec<Gamepad> {
+    let mut connected_gamepads: Vec<Gamepad> = gamepads.iter().collect();
+    connected_gamepads.sort_by_key(|gamepad| gamepad.id);
+    connected_gamepads
+}
+
+// Every local player reads their own set of keys and their own gamepad, so that several players can
+// share one machine
 pub fn read_local_inputs(
     mut commands: Commands,
     keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
     local_players: Res<LocalPlayers>,
+    selected_broadsides: Res<SelectedBroadsides>,
 ) {
     let mut local_inputs = HashMap::new();
 
-    for handle in &local_players.0 {
-        let mut input = 0u8;
-        if keys.any_pressed([KeyCode::ArrowLeft, KeyCode::KeyA]) {
-            input |= INPUT_LEFT
+    let connected_gamepads = connected_gamepads(&gamepads);
+    let num_local_players = local_players.0.len();
+
+    for (local_player, handle) in local_players.0.iter().enumerate() {
+        let sets = bindings.sets(local_player, num_local_players);
+        let pressed = |action| sets.iter().any(|set| set.pressed(&keys, action));
+
+        let gamepad = connected_gamepads.get(local_player).copied();
+        let gamepad_pressed = |button_type| {
+            gamepad.is_some_and(|gamepad| {
+                gamepad_buttons.pressed(GamepadButton::new(gamepad, button_type))
+            })
+        };
+        let selected_broadside = selected_broadsides
+            .0
+            .get(&local_player)
+            .copied()
+            .unwrap_or_default();
+
+        let steering = gamepad
+            .and_then(|gamepad| {
+                gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            })
+            .map(PlayerInput::quantize_steering)
+            .unwrap_or_default();
+
+        let mut buttons = 0u8;
+        if pressed(InputAction::LetOutSail) || gamepad_pressed(GamepadButtonType::DPadUp) {
+            buttons |= INPUT_UP;
         }
-        if keys.any_pressed([KeyCode::ArrowRight, KeyCode::KeyD]) {
-            input |= INPUT_RIGHT;
+        if pressed(InputAction::ReefSail) || gamepad_pressed(GamepadButtonType::DPadDown) {
+            buttons |= INPUT_DOWN;
         }
-        if keys.any_pressed([KeyCode::Space, KeyCode::Enter]) {
-            input |= INPUT_FIRE;
+        if pressed(InputAction::TurnPort) {
+            buttons |= INPUT_LEFT
+        }
+        if pressed(InputAction::TurnStarboard) {
+            buttons |= INPUT_RIGHT;
+        }
+        if pressed(InputAction::Fire)
+            || gamepad_pressed(GamepadButtonType::LeftTrigger2)
+            || gamepad_pressed(GamepadButtonType::RightTrigger2)
+        {
+            buttons |= INPUT_FIRE;
         }
 
-        local_inputs.insert(*handle, input);
+        // A trigger fires the broadside on its own side from the frame it is pressed, since the
+        // selection is only updated after the inputs are read
+        let broadside = match (
+            gamepad_pressed(GamepadButtonType::LeftTrigger2),
+            gamepad_pressed(GamepadButtonType::RightTrigger2),
+        ) {
+            (true, false) => Broadside::Port,
+            (false, true) => Broadside::Starboard,
+            _ => selected_broadside,
+        };
+        if broadside == Broadside::Starboard {
+            buttons |= INPUT_STARBOARD;
+        }
+
+        local_inputs.insert(*handle, PlayerInput { buttons, steering });
     }
 
     commands.insert_resource(LocalInputs::<RollbackConfig>(local_inputs));
 }
 
+pub fn select_broadsides(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    local_players: Res<LocalPlayers>,
+    mut selected_broadsides: ResMut<SelectedBroadsides>,
+) {
+    let connected_gamepads = connected_gamepads(&gamepads);
+    let num_local_players = local_players.0.len();
+
+    for local_player in 0..num_local_players {
+        let sets = bindings.sets(local_player, num_local_players);
+        let just_pressed = |action| sets.iter().any(|set| set.just_pressed(&keys, action));
+
+        let gamepad = connected_gamepads.get(local_player).copied();
+        let gamepad_just_pressed = |button_type| {
+            gamepad.is_some_and(|gamepad| {
+                gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type))
+            })
+        };
+
+        if just_pressed(InputAction::PortBroadside)
+            || gamepad_just_pressed(GamepadButtonType::LeftTrigger2)
+        {
+            selected_broadsides.0.insert(local_player, Broadside::Port);
+        }
+        if just_pressed(InputAction::StarboardBroadside)
+            || gamepad_just_pressed(GamepadButtonType::RightTrigger2)
+        {
+            selected_broadsides
+                .0
+                .insert(local_player, Broadside::Starboard);
+        }
+    }
+}
+
 pub fn read_mouse_input(
     mut mouse_motion_event_reader: EventReader<MouseMotion>,
     mut orbit_motion_event_writer: EventWriter<OrbitMotion>,
 ) {
     for mouse_motion_event in mouse_motion_event_reader.read() {
+        // The mouse orbits the camera of the first local player
         orbit_motion_event_writer.send(OrbitMotion {
+            local_player: 0,
             delta: mouse_motion_event.delta,
         });
     }
 }
+
+// Orbits the camera like a mouse moving at a speed proportional to the right stick deflection
+pub fn read_gamepad_orbit_input(
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut orbit_motion_event_writer: EventWriter<OrbitMotion>,
+    time: Res<Time>,
+) {
+    for (local_player, gamepad) in connected_gamepads(&gamepads).into_iter().enumerate() {
+        let stick = Vec2::new(
+            gamepad_axes
+                .get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickX))
+                .unwrap_or_default(),
+            gamepad_axes
+                .get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY))
+                .unwrap_or_default(),
+        );
+
+        if stick != Vec2::ZERO {
+            // Screen space y grows downwards, stick y upwards
+            orbit_motion_event_writer.send(OrbitMotion {
+                local_player,
+                delta: Vec2::new(stick.x, -stick.y) * GAMEPAD_ORBIT_SPEED * time.delta_seconds(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::broadside;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInfo};
+    use bevy::input::InputPlugin;
+
+    #[test]
+    fn fresh_trigger_press_fires_the_broadside_on_its_side() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let gamepad = Gamepad::new(0);
+        app.world.send_event(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected(GamepadInfo {
+                name: "gamepad".to_string(),
+            }),
+        ));
+        app.update();
+
+        app.insert_resource(InputBindings { players: vec![] });
+        app.insert_resource(LocalPlayers(vec![0]));
+        app.init_resource::<SelectedBroadsides>();
+
+        // Pressed after the inputs of the previous frame were read, with port still selected
+        app.world
+            .resource_mut::<ButtonInput<GamepadButton>>()
+            .press(GamepadButton::new(
+                gamepad,
+                GamepadButtonType::RightTrigger2,
+            ));
+        app.world.run_system_once(read_local_inputs);
+
+        let input = app.world.resource::<LocalInputs<RollbackConfig>>().0[&0];
+        assert!(input.buttons & INPUT_FIRE != 0);
+        assert_eq!(broadside(input), Broadside::Starboard);
+    }
+}
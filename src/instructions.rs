@@ -1,13 +1,76 @@
-use crate::camera::systems::spawn_camera;
-use crate::instructions::systems::display_control_keys;
+use crate::game_state::states::GameState;
+use crate::inputs::resources::InputBindings;
+use crate::inputs::systems::load_input_bindings;
+use crate::instructions::systems::{display_control_keys, update_control_keys};
 use bevy::prelude::*;
 
+mod components;
 mod systems;
 
 pub struct InstructionsPlugin;
 
 impl Plugin for InstructionsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, display_control_keys.after(spawn_camera));
+        // The key bindings are only known once the assets are loaded
+        app.add_systems(
+            OnExit(GameState::LoadingAssets),
+            display_control_keys
+                .after(load_input_bindings)
+                .run_if(resource_exists::<InputBindings>),
+        );
+
+        // Keep the instructions in line with the key bindings when they are rebound
+        app.add_systems(
+            Update,
+            update_control_keys.run_if(resource_exists_and_changed::<InputBindings>),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::resources::Args;
+    use crate::camera::resources::MainCamera;
+    use crate::instructions::components::ControlKeysText;
+    use clap::Parser;
+
+    fn instructions_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_state::<GameState>();
+        app.add_plugins(InstructionsPlugin);
+
+        let camera = app.world.spawn_empty().id();
+        app.insert_resource(MainCamera { id: camera });
+        app.insert_resource(Args::parse_from(["pirate-sea-jam"]));
+
+        app
+    }
+
+    #[test]
+    fn instructions_wait_for_the_key_bindings_to_load() {
+        let mut app = instructions_app();
+
+        // Startup and the first update run before any asset has loaded
+        app.update();
+
+        let mut text_query = app.world.query_filtered::<(), With<ControlKeysText>>();
+        assert_eq!(text_query.iter(&app.world).count(), 0);
+    }
+
+    #[test]
+    fn instructions_show_the_loaded_key_bindings() {
+        let mut app = instructions_app();
+        app.update();
+
+        app.insert_resource(InputBindings { players: vec![] });
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::SplashScreen);
+        app.update();
+
+        let mut text_query = app.world.query_filtered::<(), With<ControlKeysText>>();
+        assert_eq!(text_query.iter(&app.world).count(), 1);
     }
 }
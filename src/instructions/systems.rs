@@ -1,7 +1,53 @@
+use crate::args::resources::Args;
 use crate::camera::resources::MainCamera;
+use crate::inputs::resources::{InputAction, InputBindings, KeyBindingSet};
+use crate::instructions::components::ControlKeysText;
 use bevy::prelude::*;
 
-pub fn display_control_keys(mut commands: Commands, main_camera: Res<MainCamera>) {
+fn control_keys_text(args: &Args, bindings: &InputBindings) -> String {
+    let end_match = format!(
+        "[{}] {}",
+        bindings.shared_key_label(InputAction::EndMatch),
+        InputAction::EndMatch.description()
+    );
+
+    if args.spectate {
+        return format!("[Tab] follow next ship | [Mouse] orbit camera | {end_match}");
+    }
+
+    // Every player of a local session plays on this machine, each with their own keys
+    let num_local_players = if args.sync_test { args.num_players } else { 1 };
+
+    if num_local_players == 1 {
+        let player_keys = bindings
+            .players
+            .first()
+            .map(KeyBindingSet::control_keys_text)
+            .unwrap_or_default();
+
+        return format!("{player_keys} | [Mouse] orbit camera | {end_match}");
+    }
+
+    let player_keys = bindings
+        .players
+        .iter()
+        .take(num_local_players)
+        .enumerate()
+        .map(|(player, set)| format!("Player {}: {}", player + 1, set.control_keys_text()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("{player_keys}\n[Mouse] orbit camera | {end_match}")
+}
+
+pub fn display_control_keys(
+    mut commands: Commands,
+    main_camera: Res<MainCamera>,
+    args: Res<Args>,
+    bindings: Res<InputBindings>,
+) {
+    let control_keys = control_keys_text(&args, &bindings);
+
     commands
         .spawn((
             // Seems to be required in dev builds since using editor plugin results in multiple
@@ -25,13 +71,26 @@ pub fn display_control_keys(mut commands: Commands, main_camera: Res<MainCamera>
             },
         ))
         .with_children(|child_builder| {
-            child_builder.spawn(TextBundle::from_section(
-                "[A] turn port | [D] turn starboard | [Space] fire cannons | [Mouse] orbit camera",
-                TextStyle {
-                    font_size: 18.0,
-                    color: Color::WHITE,
-                    ..default()
-                },
+            child_builder.spawn((
+                ControlKeysText,
+                TextBundle::from_section(
+                    control_keys,
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
             ));
         });
 }
+
+pub fn update_control_keys(
+    mut text_query: Query<&mut Text, With<ControlKeysText>>,
+    args: Res<Args>,
+    bindings: Res<InputBindings>,
+) {
+    for mut text in &mut text_query {
+        text.sections[0].value = control_keys_text(&args, &bindings);
+    }
+}
@@ -0,0 +1,43 @@
+mod components;
+mod resources;
+mod systems;
+
+use crate::game_state::states::GameState;
+use crate::inputs::resources::InputBindings;
+use crate::key_bindings_menu::resources::{EditedPlayer, RebindingAction};
+use crate::key_bindings_menu::systems::{
+    despawn_key_bindings_menu, handle_key_bindings_interactions, rebind_pressed_key,
+    spawn_key_bindings_menu, update_key_binding_labels,
+};
+use bevy::prelude::*;
+
+pub const KEY_BINDINGS_BUTTON_NORMAL: Color = Color::rgb(0.9, 0.45, 0.21);
+pub const KEY_BINDINGS_BUTTON_HOVER: Color = Color::rgb(0.87, 0.36, 0.18);
+
+// Lists the key of every action for each player sharing the keyboard, which can be clicked to
+// rebind it to the next key pressed
+pub struct KeyBindingsMenuPlugin;
+
+impl Plugin for KeyBindingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RebindingAction>();
+        app.init_resource::<EditedPlayer>();
+
+        app.add_systems(OnEnter(GameState::KeyBindings), spawn_key_bindings_menu)
+            .add_systems(OnExit(GameState::KeyBindings), despawn_key_bindings_menu)
+            .add_systems(
+                Update,
+                (
+                    handle_key_bindings_interactions,
+                    rebind_pressed_key,
+                    update_key_binding_labels.run_if(
+                        resource_exists_and_changed::<InputBindings>
+                            .or_else(resource_changed::<RebindingAction>)
+                            .or_else(resource_changed::<EditedPlayer>),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::KeyBindings)),
+            );
+    }
+}
@@ -0,0 +1,18 @@
+use crate::inputs::resources::InputAction;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct KeyBindingsMenuLayout;
+
+#[derive(Component)]
+pub enum KeyBindingsButton {
+    NextPlayer,
+    Rebind(InputAction),
+    Back,
+}
+
+#[derive(Component)]
+pub struct KeyBindingLabel(pub InputAction);
+
+#[derive(Component)]
+pub struct EditedPlayerLabel;
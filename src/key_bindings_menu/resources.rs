@@ -0,0 +1,10 @@
+use crate::inputs::resources::InputAction;
+use bevy::prelude::*;
+
+// The action waiting for a key press to be bound to
+#[derive(Resource, Default)]
+pub struct RebindingAction(pub Option<InputAction>);
+
+// Index of the player sharing the keyboard whose keys are shown and rebound
+#[derive(Resource, Default)]
+pub struct EditedPlayer(pub usize);
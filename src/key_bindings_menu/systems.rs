@@ -0,0 +1,218 @@
+use crate::assets::resources::FontAssets;
+use crate::camera::resources::MainCamera;
+use crate::game_state::states::GameState;
+use crate::inputs::resources::{InputAction, InputBindings};
+use crate::key_bindings_menu::components::{
+    EditedPlayerLabel, KeyBindingLabel, KeyBindingsButton, KeyBindingsMenuLayout,
+};
+use crate::key_bindings_menu::resources::{EditedPlayer, RebindingAction};
+use crate::key_bindings_menu::{KEY_BINDINGS_BUTTON_HOVER, KEY_BINDINGS_BUTTON_NORMAL};
+use bevy::prelude::*;
+
+fn edited_player_label(edited_player: &EditedPlayer) -> String {
+    format!("Player {} keys", edited_player.0 + 1)
+}
+
+fn key_binding_button_bundle(width: f32) -> ButtonBundle {
+    ButtonBundle {
+        style: Style {
+            width: Val::Px(width),
+            height: Val::Px(56.0),
+            border: UiRect::all(Val::Px(6.0)),
+            // horizontally center child text
+            justify_content: JustifyContent::Center,
+            // vertically center child text
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        border_color: BorderColor(Color::WHITE),
+        background_color: KEY_BINDINGS_BUTTON_NORMAL.into(),
+        ..default()
+    }
+}
+
+pub fn spawn_key_bindings_menu(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    main_camera: Res<MainCamera>,
+    bindings: Res<InputBindings>,
+    edited_player: Res<EditedPlayer>,
+) {
+    let text_style = TextStyle {
+        font: font_assets.font_handles["the-bomb-regular.otf"].clone(),
+        font_size: 32.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            // Seems to be required in dev builds since using editor plugin results in multiple
+            // cameras
+            TargetCamera(main_camera.id),
+            KeyBindingsMenuLayout,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(12.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|child_builder| {
+            child_builder.spawn(TextBundle::from_section(
+                "Key Bindings",
+                TextStyle {
+                    font_size: 40.0,
+                    ..text_style.clone()
+                },
+            ));
+
+            child_builder
+                .spawn((
+                    KeyBindingsButton::NextPlayer,
+                    key_binding_button_bundle(320.0),
+                ))
+                .with_children(|button_builder| {
+                    button_builder.spawn((
+                        EditedPlayerLabel,
+                        TextBundle::from_section(
+                            edited_player_label(&edited_player),
+                            text_style.clone(),
+                        ),
+                    ));
+                });
+
+            for action in InputAction::ALL {
+                child_builder
+                    .spawn(NodeBundle {
+                        style: Style {
+                            column_gap: Val::Px(16.0),
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row_builder| {
+                        row_builder.spawn(
+                            TextBundle::from_section(action.description(), text_style.clone())
+                                .with_style(Style {
+                                    width: Val::Px(360.0),
+                                    ..default()
+                                }),
+                        );
+
+                        row_builder
+                            .spawn((
+                                KeyBindingsButton::Rebind(action),
+                                key_binding_button_bundle(240.0),
+                            ))
+                            .with_children(|button_builder| {
+                                button_builder.spawn((
+                                    KeyBindingLabel(action),
+                                    TextBundle::from_section(
+                                        bindings.key_label(edited_player.0, action),
+                                        text_style.clone(),
+                                    ),
+                                ));
+                            });
+                    });
+            }
+
+            child_builder
+                .spawn((KeyBindingsButton::Back, key_binding_button_bundle(320.0)))
+                .with_children(|button_builder| {
+                    button_builder.spawn(TextBundle::from_section("Back", text_style.clone()));
+                });
+        });
+}
+
+pub fn despawn_key_bindings_menu(
+    mut commands: Commands,
+    key_bindings_menu_query: Query<Entity, With<KeyBindingsMenuLayout>>,
+    mut rebinding_action: ResMut<RebindingAction>,
+) {
+    for entity in &key_bindings_menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    rebinding_action.0 = None;
+}
+
+pub fn handle_key_bindings_interactions(
+    mut button_query: Query<
+        (&Interaction, &KeyBindingsButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut rebinding_action: ResMut<RebindingAction>,
+    mut edited_player: ResMut<EditedPlayer>,
+    bindings: Res<InputBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button, mut background_color) in &mut button_query {
+        match *interaction {
+            Interaction::Pressed => match button {
+                KeyBindingsButton::NextPlayer => {
+                    edited_player.0 = (edited_player.0 + 1) % bindings.players.len().max(1);
+                    rebinding_action.0 = None;
+                }
+                KeyBindingsButton::Rebind(action) => rebinding_action.0 = Some(*action),
+                KeyBindingsButton::Back => next_state.set(GameState::SplashScreen),
+            },
+            Interaction::Hovered => {
+                *background_color = KEY_BINDINGS_BUTTON_HOVER.into();
+            }
+            Interaction::None => {
+                *background_color = KEY_BINDINGS_BUTTON_NORMAL.into();
+            }
+        }
+    }
+}
+
+// Binds the next key pressed to the action waiting for it, or cancels on escape. Bindings are
+// saved right away, so that they persist across sessions.
+pub fn rebind_pressed_key(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut rebinding_action: ResMut<RebindingAction>,
+    edited_player: Res<EditedPlayer>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    let Some(action) = rebinding_action.0 else {
+        return;
+    };
+
+    let Some(key) = keys.get_just_pressed().next() else {
+        return;
+    };
+
+    if *key != KeyCode::Escape {
+        bindings.rebind(edited_player.0, action, *key);
+        bindings.save();
+    }
+
+    rebinding_action.0 = None;
+}
+
+pub fn update_key_binding_labels(
+    mut label_query: Query<(&mut Text, &KeyBindingLabel), Without<EditedPlayerLabel>>,
+    mut edited_player_label_query: Query<&mut Text, With<EditedPlayerLabel>>,
+    rebinding_action: Res<RebindingAction>,
+    edited_player: Res<EditedPlayer>,
+    bindings: Res<InputBindings>,
+) {
+    for (mut text, label) in &mut label_query {
+        text.sections[0].value = if rebinding_action.0 == Some(label.0) {
+            "press a key".to_string()
+        } else {
+            bindings.key_label(edited_player.0, label.0)
+        };
+    }
+
+    for mut text in &mut edited_player_label_query {
+        text.sections[0].value = edited_player_label(&edited_player);
+    }
+}
@@ -7,6 +7,7 @@
 #![cfg_attr(not(debug_assertions), allow(dead_code, unused_imports))]
 
 use crate::args::ArgsPlugin;
+use crate::artillery::{AimPredictionPlugin, ArtilleryAnimationPlugin, ReloadIndicatorPlugin};
 use crate::connection::systems::RollbackConfig;
 use crate::controls::components::Controls;
 use crate::game_state::states::GameState;
@@ -17,15 +18,22 @@ use bevy_ggrs::{GgrsApp, GgrsPlugin};
 
 use crate::connection::FPS;
 use crate::debug_fps::DebugFpsPlugin;
+use crate::desync_report::DesyncReportPlugin;
+use crate::disconnect::DisconnectNoticePlugin;
 use crate::focal_point::FocalPointPlugin;
 use crate::instructions::InstructionsPlugin;
+use crate::key_bindings_menu::KeyBindingsMenuPlugin;
 use crate::menu::MenuPlugin;
 use crate::orbiting_camera::OrbitingCameraPlugin;
-use crate::physics::PhysicsPlugin;
+use crate::player::PlayerAnimationPlugin;
+use crate::post_match::PostMatchPlugin;
+use crate::replay::ReplayPlugin;
+use crate::rollback_audit::RollbackAuditPlugin;
+use crate::simulation::SimulationPlugins;
 use crate::sky_box::SkyBoxPlugin;
+use crate::spectator::SpectatorPlugin;
 use crate::sync_test::SyncTestPlugin;
 use crate::widget_debug::WidgetDebugPlugin;
-use crate::wind::WindPlugin;
 
 mod args;
 mod artillery;
@@ -33,18 +41,28 @@ mod assets;
 mod camera;
 mod connection;
 mod controls;
+mod damage;
 mod debug_fps;
+mod desync_report;
+mod disconnect;
 mod focal_point;
 mod game_state;
+mod headless;
 mod inputs;
 mod instructions;
+mod key_bindings_menu;
 mod light;
 mod menu;
 mod ocean;
 mod orbiting_camera;
 mod physics;
 mod player;
+mod post_match;
+mod replay;
+mod rollback_audit;
+mod simulation;
 mod sky_box;
+mod spectator;
 mod stats;
 mod sync_test;
 mod utils;
@@ -52,6 +70,23 @@ mod widget_debug;
 mod wind;
 
 fn main() {
+    let args = args::parse_args();
+    if let Some(frames) = args.headless_frames {
+        if let Err(error) = headless::run(args, frames) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(report_paths) = &args.diff_desync_reports {
+        if let Err(error) = desync_report::diff(&report_paths[0], &report_paths[1]) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let mut app = App::new();
 
     app.insert_resource(AssetMetaCheck::Never);
@@ -69,25 +104,31 @@ fn main() {
     // define frequency of rollback game logic update
     app.set_rollback_schedule_fps(FPS);
 
+    app.add_plugins(SimulationPlugins);
+
     app.add_plugins(light::LightPlugin);
     app.add_plugins(camera::CameraPlugin);
     app.add_plugins(ocean::OceanPlugin);
-    app.add_plugins(player::PlayerPlugin);
-    app.add_plugins(controls::ShipPlugin);
+    app.add_plugins(PlayerAnimationPlugin);
     app.add_plugins(assets::AssetsPlugin);
     app.add_plugins(inputs::InputsPlugin);
     app.add_plugins(connection::ConnectionPlugin);
-    app.add_plugins(artillery::ArtilleryPlugin);
+    app.add_plugins(ArtilleryAnimationPlugin);
+    app.add_plugins(ReloadIndicatorPlugin);
+    app.add_plugins(AimPredictionPlugin);
     app.add_plugins(ArgsPlugin);
+    app.add_plugins(ReplayPlugin);
     app.add_plugins(SyncTestPlugin);
     app.add_plugins(FocalPointPlugin);
+    app.add_plugins(SpectatorPlugin);
     app.add_plugins(OrbitingCameraPlugin);
-    app.add_plugins(WindPlugin);
     app.add_plugins(SkyBoxPlugin);
     app.add_plugins(DebugFpsPlugin);
     app.add_plugins(InstructionsPlugin);
+    app.add_plugins(DisconnectNoticePlugin);
     app.add_plugins(MenuPlugin);
-    app.add_plugins(PhysicsPlugin);
+    app.add_plugins(KeyBindingsMenuPlugin);
+    app.add_plugins(PostMatchPlugin);
 
     app.register_type::<Controls>();
 
@@ -100,5 +141,11 @@ fn main() {
     #[cfg(debug_assertions)]
     app.add_plugins(stats::StatsPlugin);
 
+    #[cfg(debug_assertions)]
+    app.add_plugins(DesyncReportPlugin);
+
+    #[cfg(debug_assertions)]
+    app.add_plugins(RollbackAuditPlugin);
+
     app.run();
 }
@@ -1,7 +1,12 @@
 use bevy::prelude::*;
 
 #[derive(Component)]
-pub struct StartGameButton;
+pub enum MainMenuButton {
+    StartGame,
+    // Cycles through the ship classes in the shipyard
+    ShipClass,
+    KeyBindings,
+}
 
 #[derive(Component)]
 pub struct StartMenuLayout;
@@ -1,8 +1,10 @@
+use crate::args::resources::Args;
 use crate::assets::resources::FontAssets;
 use crate::camera::resources::MainCamera;
 use crate::game_state::states::GameState;
-use crate::menu::components::{StartGameButton, StartMenuLayout};
+use crate::menu::components::{MainMenuButton, StartMenuLayout};
 use crate::menu::{START_BUTTON_HOVER, START_BUTTON_NORMAL};
+use crate::player::definitions::Shipyard;
 use bevy::prelude::*;
 
 // Start game button mainly used for determining input device as well as focusing canvas element when
@@ -11,7 +13,11 @@ pub fn spawn_main_menu(
     mut commands: Commands,
     font_assets: Res<FontAssets>,
     main_camera: Res<MainCamera>,
+    args: Res<Args>,
+    shipyard: Res<Shipyard>,
 ) {
+    let ship_class_label = ship_class_label(&shipyard.ship(&args.hull).name);
+
     commands
         .spawn((
             // Seems to be required in dev builds since using editor plugin results in multiple
@@ -22,6 +28,8 @@ pub fn spawn_main_menu(
                 style: Style {
                     width: Val::Percent(100.0),
                     height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(16.0),
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
                     ..default()
@@ -30,35 +38,41 @@ pub fn spawn_main_menu(
             },
         ))
         .with_children(|child_builder| {
-            child_builder
-                .spawn((
-                    StartGameButton,
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(320.0),
-                            height: Val::Px(64.0),
-                            border: UiRect::all(Val::Px(8.0)),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
+            for (button, label) in [
+                (MainMenuButton::StartGame, "Start Game".to_string()),
+                (MainMenuButton::ShipClass, ship_class_label),
+                (MainMenuButton::KeyBindings, "Key Bindings".to_string()),
+            ] {
+                child_builder
+                    .spawn((
+                        button,
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(320.0),
+                                height: Val::Px(64.0),
+                                border: UiRect::all(Val::Px(8.0)),
+                                // horizontally center child text
+                                justify_content: JustifyContent::Center,
+                                // vertically center child text
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            border_color: BorderColor(Color::WHITE),
+                            background_color: START_BUTTON_NORMAL.into(),
                             ..default()
                         },
-                        border_color: BorderColor(Color::WHITE),
-                        background_color: START_BUTTON_NORMAL.into(),
-                        ..default()
-                    },
-                ))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Start Game",
-                        TextStyle {
-                            font: font_assets.font_handles["the-bomb-regular.otf"].clone(),
-                            font_size: 40.0,
-                            color: Color::WHITE,
-                        },
-                    ));
-                });
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            label,
+                            TextStyle {
+                                font: font_assets.font_handles["the-bomb-regular.otf"].clone(),
+                                font_size: 40.0,
+                                color: Color::WHITE,
+                            },
+                        ));
+                    });
+            }
         });
 }
 
@@ -71,18 +85,47 @@ pub fn despawn_main_menu(
     }
 }
 
+fn ship_class_label(name: &str) -> String {
+    format!("Ship: {name}")
+}
+
 pub fn handle_main_menu_interactions(
     mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<StartGameButton>),
+        (
+            &Interaction,
+            &MainMenuButton,
+            &mut BackgroundColor,
+            &Children,
+        ),
+        Changed<Interaction>,
     >,
+    mut text_query: Query<&mut Text>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut args: ResMut<Args>,
+    shipyard: Res<Shipyard>,
 ) {
-    for (interaction, mut background_color) in &mut button_query {
+    for (interaction, button, mut background_color, children) in &mut button_query {
         match *interaction {
-            Interaction::Pressed => {
-                next_state.set(GameState::Matchmaking);
-            }
+            Interaction::Pressed => match button {
+                MainMenuButton::StartGame => next_state.set(GameState::Matchmaking),
+                MainMenuButton::ShipClass => {
+                    // The picked class is announced to the other peers when joining a room
+                    let current = shipyard
+                        .ships
+                        .iter()
+                        .position(|ship| ship.name.eq_ignore_ascii_case(&args.hull))
+                        .unwrap_or_default();
+                    let next = &shipyard.ships[(current + 1) % shipyard.ships.len()];
+                    args.hull = next.name.clone();
+
+                    for child in children {
+                        if let Ok(mut text) = text_query.get_mut(*child) {
+                            text.sections[0].value = ship_class_label(&next.name);
+                        }
+                    }
+                }
+                MainMenuButton::KeyBindings => next_state.set(GameState::KeyBindings),
+            },
             Interaction::Hovered => {
                 *background_color = START_BUTTON_HOVER.into();
             }
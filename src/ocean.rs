@@ -1,22 +1,27 @@
+use crate::args::run_conditions::wind_driven_waves_mode;
+use crate::artillery::systems::hit_water;
+use crate::desync_report::ChecksumReportApp;
 use crate::focal_point::resources::FocalPoint;
 use crate::game_state::states::GameState;
 use crate::ocean::materials::StandardOceanMaterial;
-use crate::ocean::resources::Wave;
+use crate::ocean::resources::{checksum_wave, Wave};
 use crate::ocean::systems::{
-    spawn_ocean, sync_ocean_tiles_center_offset, sync_shader_time, update_buoy_water_height,
-    update_water_drag,
+    advance_waves, advance_waves_with_time, raise_waves_from_wind, spawn_ocean,
+    sync_ocean_tiles_center_offset, sync_shader_waves, update_buoy_water_height,
+    update_hull_water_heights, update_water_drag,
 };
 use crate::physics::systems::{
-    update_aerodynamic_force, update_buoyant_force, update_linear_drag_force,
+    update_buoyant_force, update_hull_buoyant_force, update_linear_drag_force,
 };
+use crate::wind::systems::update_wind;
 use bevy::asset::load_internal_asset;
 use bevy::prelude::*;
-use bevy_ggrs::GgrsSchedule;
+use bevy_ggrs::{GgrsApp, GgrsSchedule};
 
 mod components;
 mod materials;
 pub mod resources;
-mod systems;
+pub mod systems;
 
 pub const OCEAN_TILE_SIZE: f32 = 160.;
 
@@ -34,7 +39,7 @@ const OCEAN_MATERIAL_BINDINGS: Handle<Shader> =
     Handle::weak_from_u128(0x06a957f34bac4aabad104c64a301c3fb);
 
 // Each Vec4 containing direction x, direction z, steepness, wave_length. Sum of all steepness values must not exceed 1.
-const WAVES: [Vec4; 4] = [
+pub const WAVES: [Vec4; 4] = [
     Vec4::new(1., 0., 0.22, 64.),
     Vec4::new(-1., 0.8, 0.2, 48.),
     Vec4::new(1., -1.2, 0.18, 32.),
@@ -49,6 +54,10 @@ const WAVES: [Vec4; 4] = [
 //     Vec4::new(1., 0., 0., 1.),
 // ];
 
+// Seconds the waves raised by the wind take to follow a change in it, long enough for gusts to
+// barely show
+const WAVE_RESPONSE_SECONDS: f32 = 20.;
+
 const OCEAN_ANIMATION_TIME_SCALE: f32 = 0.6;
 
 pub struct OceanPlugin;
@@ -82,12 +91,6 @@ impl Plugin for OceanPlugin {
             Shader::from_wgsl
         );
 
-        app.insert_resource(Wave {
-            time_scale: OCEAN_ANIMATION_TIME_SCALE,
-            sample_count: 4,
-            configs: WAVES,
-        });
-
         app.add_plugins(MaterialPlugin::<StandardOceanMaterial>::default());
 
         app.add_systems(Startup, spawn_ocean);
@@ -97,21 +100,63 @@ impl Plugin for OceanPlugin {
             sync_ocean_tiles_center_offset.run_if(resource_changed::<FocalPoint>),
         );
 
-        // Animate waves (outside GGRS schedule) when displaying main menu
+        // Animate waves (outside GGRS schedule) when displaying main menu or key bindings
         app.add_systems(
             Update,
-            sync_shader_time.run_if(in_state(GameState::SplashScreen)),
+            advance_waves_with_time.run_if(
+                in_state(GameState::SplashScreen).or_else(in_state(GameState::KeyBindings)),
+            ),
         );
 
+        app.add_systems(
+            Update,
+            sync_shader_waves
+                .after(advance_waves_with_time)
+                .run_if(resource_changed::<Wave>),
+        );
+    }
+}
+
+// Wave sampling used by the rollback simulation, independent of rendering the ocean
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Wave {
+            time_scale: OCEAN_ANIMATION_TIME_SCALE,
+            sample_count: 4,
+            configs: WAVES,
+            phases: [0.; 4],
+        });
+
+        app.rollback_resource_with_clone::<Wave>();
+        app.checksum_and_report_resource::<Wave>(checksum_wave);
+
+        // Raised and moved along before anything samples the water of the frame
+        app.add_systems(
+            GgrsSchedule,
+            raise_waves_from_wind
+                .after(update_wind)
+                .run_if(wind_driven_waves_mode),
+        );
         app.add_systems(
             GgrsSchedule,
-            sync_shader_time.before(update_aerodynamic_force),
+            advance_waves
+                .after(raise_waves_from_wind)
+                .before(update_buoy_water_height)
+                .before(update_hull_water_heights)
+                .before(update_water_drag)
+                .before(hit_water),
         );
 
         app.add_systems(
             GgrsSchedule,
             update_buoy_water_height.before(update_buoyant_force),
         );
+        app.add_systems(
+            GgrsSchedule,
+            update_hull_water_heights.before(update_hull_buoyant_force),
+        );
         app.add_systems(
             GgrsSchedule,
             update_water_drag.before(update_linear_drag_force),
@@ -10,7 +10,6 @@ pub struct OceanTileSettings {
     pub tile_size: f32,
     pub quad_cell_size: f32,
     pub tier: u32,
-    pub time_scale: f32,
     pub waves: [Vec4; 4],
     pub subdivision_count: u32,
 }
@@ -20,10 +19,11 @@ pub struct OceanPosition {
     pub center_offset: Vec3,
 }
 
+// Phase of each wave, packed into a single vector, which also keeps the size a multiple of 16 bytes
+// as wasm needs
 #[derive(ShaderType, Clone, Reflect, Debug, Default)]
-pub struct RollbackTime {
-    pub elapsed_seconds: f32,
-    pub padding: Vec3, // Needed for wasm, so that type has a size that is a multiple of 16 bytes
+pub struct WavePhases {
+    pub phases: Vec4,
 }
 
 #[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
@@ -37,7 +37,7 @@ pub struct OceanMaterialExtension {
     pub position: OceanPosition,
 
     #[uniform(102)]
-    pub rollback_time: RollbackTime,
+    pub wave_phases: WavePhases,
 }
 
 impl MaterialExtension for OceanMaterialExtension {
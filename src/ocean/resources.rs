@@ -1,23 +1,57 @@
+use crate::utils::hash::hash_f32_number;
 use crate::utils::water_mechanics;
 use bevy::prelude::*;
+use bevy::utils::FixedState;
+use std::f32::consts::TAU;
+use std::hash::{BuildHasher, Hash, Hasher};
 
-#[derive(Resource, Reflect, Default)]
+// Rolled back, since the waves follow the wind of the simulated frames when raised by the wind and
+// travel with every simulated frame
+#[derive(Resource, Reflect, Clone, Default)]
 #[reflect(Resource)]
 pub struct Wave {
     pub time_scale: f32,
     pub sample_count: u8,
     pub configs: [Vec4; 4],
+    // Phase each wave has travelled, wrapped to a full turn
+    pub phases: [f32; 4],
+}
+
+pub fn checksum_wave(value: &Wave) -> u64 {
+    let mut hasher = FixedState.build_hasher();
+    hash_f32_number(value.time_scale).hash(&mut hasher);
+    value.sample_count.hash(&mut hasher);
+    for config in value.configs {
+        for component in config.to_array() {
+            hash_f32_number(component).hash(&mut hasher);
+        }
+    }
+    for phase in value.phases {
+        hash_f32_number(phase).hash(&mut hasher);
+    }
+
+    #[allow(clippy::manual_hash_one)]
+    hasher.finish()
 }
 
 impl Wave {
-    pub fn next_position(&self, mut position: Vec3, waves: [Vec4; 4], time: f32) -> Vec3 {
-        let time = time * self.time_scale;
+    // Moves every wave along by its phase speed at the current wavelength
+    pub fn advance(&mut self, delta_seconds: f32) {
+        for (phase, config) in self.phases.iter_mut().zip(self.configs) {
+            *phase = (*phase
+                + water_mechanics::phase_speed(config) * self.time_scale * delta_seconds)
+                .rem_euclid(TAU);
+        }
+    }
+
+    pub fn next_position(&self, mut position: Vec3, waves: [Vec4; 4], phases: [f32; 4]) -> Vec3 {
         position.y = 0.; // Neutral water level
 
         position
             + waves
                 .into_iter()
-                .map(|wave| water_mechanics::gerstner_wave(wave, position, time))
+                .zip(phases)
+                .map(|(wave, phase)| water_mechanics::gerstner_wave(wave, position, phase))
                 .sum::<Vec3>()
     }
 
@@ -26,9 +60,8 @@ impl Wave {
         &self,
         mut position: Vec3,
         waves: [Vec4; 4],
-        time: f32,
+        phases: [f32; 4],
     ) -> (Vec3, Vec3) {
-        let time = time * self.time_scale;
         position.y = 0.; // Neutral water level
 
         let mut tangent = Vec3::new(1., 0., 0.);
@@ -37,13 +70,14 @@ impl Wave {
         let position = position
             + waves
                 .into_iter()
-                .map(|wave| {
+                .zip(phases)
+                .map(|(wave, phase)| {
                     water_mechanics::gerstner_wave_tangent_binormal(
                         wave,
                         position,
                         &mut tangent,
                         &mut binormal,
-                        time,
+                        phase,
                     )
                 })
                 .sum::<Vec3>();
@@ -53,13 +87,54 @@ impl Wave {
         (position, normal)
     }
 
-    pub fn height(&self, point: Vec3, waves: [Vec4; 4], time: f32) -> f32 {
+    pub fn height(&self, point: Vec3, waves: [Vec4; 4], phases: [f32; 4]) -> f32 {
         water_mechanics::wave_height(
             point,
             waves,
-            time,
+            phases,
             self.sample_count,
-            |position: Vec3, waves: [Vec4; 4], time: f32| self.next_position(position, waves, time),
+            |position: Vec3, waves: [Vec4; 4], phases: [f32; 4]| {
+                self.next_position(position, waves, phases)
+            },
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocean::WAVES;
+
+    #[test]
+    fn changing_wave_length_mid_match_does_not_jump_the_surface() {
+        let delta_seconds = 1. / 60.;
+        let mut wave = Wave {
+            time_scale: 1.,
+            sample_count: 4,
+            configs: WAVES,
+            ..default()
+        };
+        // Ten minutes into the match, long enough for any shift proportional to the elapsed time
+        // to be meters
+        for _ in 0..10 * 60 * 60 {
+            wave.advance(delta_seconds);
+        }
+
+        let mut stretched_wave = wave.clone();
+        for config in &mut stretched_wave.configs {
+            config.w *= 1.005;
+        }
+        wave.advance(delta_seconds);
+        stretched_wave.advance(delta_seconds);
+
+        for point in [Vec3::ZERO, Vec3::new(3., 0., -2.), Vec3::new(-5., 0., 4.)] {
+            let height = wave.height(point, wave.configs, wave.phases);
+            let stretched_height =
+                stretched_wave.height(point, stretched_wave.configs, stretched_wave.phases);
+            assert!(
+                (stretched_height - height).abs() < 0.1,
+                "surface moved from {height} to {stretched_height}"
+            );
+        }
+    }
+}
@@ -1,13 +1,17 @@
 use crate::focal_point::resources::FocalPoint;
 use crate::ocean::components::OceanTile;
 use crate::ocean::materials::{
-    OceanMaterialExtension, OceanPosition, OceanTileSettings, RollbackTime, StandardOceanMaterial,
+    OceanMaterialExtension, OceanPosition, OceanTileSettings, StandardOceanMaterial, WavePhases,
 };
 use crate::ocean::resources::Wave;
 use crate::ocean::{
     OCEAN_PRIMARY_TILE_SUBDIVISIONS, OCEAN_SECONDARY_TILE_SUBDIVISIONS, OCEAN_TILE_SIZE,
+    WAVE_RESPONSE_SECONDS,
 };
-use crate::physics::components::{AngularDrag, Buoy, LinearDrag};
+use crate::physics::components::{AngularDrag, Buoy, HullBuoyancy, LinearDrag};
+use crate::simulation::resources::SimulationClock;
+use crate::utils::water_mechanics::wind_waves;
+use crate::wind::resources::Wind;
 use bevy::math::Vec3A;
 use bevy::prelude::*;
 use bevy::render::primitives::Aabb;
@@ -35,7 +39,7 @@ pub fn spawn_ocean_tile(
     tile_size: f32,
     subdivision_count: u32,
     waves: [Vec4; 4],
-    time_scale: f32,
+    phases: [f32; 4],
     offset: Vec3,
     tier: Tier,
     commands: &mut Commands,
@@ -81,14 +85,15 @@ pub fn spawn_ocean_tile(
                         tile_size,
                         quad_cell_size: tile_size / (subdivision_count + 1) as f32,
                         tier: tier as u32,
-                        time_scale,
                         waves,
                         subdivision_count,
                     },
                     position: OceanPosition {
                         center_offset: Vec3::ZERO,
                     },
-                    rollback_time: RollbackTime::default(),
+                    wave_phases: WavePhases {
+                        phases: Vec4::from_array(phases),
+                    },
                 },
             }),
             ..default()
@@ -110,7 +115,7 @@ pub fn spawn_ocean(
         OCEAN_TILE_SIZE,
         OCEAN_PRIMARY_TILE_SUBDIVISIONS,
         wave.configs,
-        wave.time_scale,
+        wave.phases,
         Vec3::ZERO,
         Tier::Primary,
         &mut commands,
@@ -124,7 +129,7 @@ pub fn spawn_ocean(
             OCEAN_TILE_SIZE,
             OCEAN_SECONDARY_TILE_SUBDIVISIONS,
             wave.configs,
-            wave.time_scale,
+            wave.phases,
             offset_base * OCEAN_TILE_SIZE,
             Tier::Secondary,
             &mut commands,
@@ -137,7 +142,7 @@ pub fn spawn_ocean(
             OCEAN_TILE_SIZE * 3.,
             0,
             wave.configs,
-            wave.time_scale,
+            wave.phases,
             offset_base * OCEAN_TILE_SIZE * 3.,
             Tier::Tertiary,
             &mut commands,
@@ -161,31 +166,76 @@ pub fn sync_ocean_tiles_center_offset(
     }
 }
 
-pub fn sync_shader_time(time: Res<Time>, mut materials: ResMut<Assets<StandardOceanMaterial>>) {
+// Moves the waves along with the time outside of a match, where nothing simulates them
+pub fn advance_waves_with_time(time: Res<Time>, mut wave: ResMut<Wave>) {
+    wave.advance(time.delta_seconds());
+}
+
+// Waves ease towards the ones the wind of the frame raises rather than following every gust, since
+// changing waves shifts the whole sea. A session starts out on the waves of its first wind.
+pub fn raise_waves_from_wind(mut wave: ResMut<Wave>, wind: Res<Wind>, clock: Res<SimulationClock>) {
+    let target_configs = wind_waves(wind.0);
+    if clock.frame <= 1 {
+        wave.configs = target_configs;
+        return;
+    }
+
+    let response = 1. - (-clock.delta_seconds / WAVE_RESPONSE_SECONDS).exp();
+    for (config, target_config) in wave.configs.iter_mut().zip(target_configs) {
+        *config = config.lerp(target_config, response);
+    }
+}
+
+// Waves travel a frame at a time at the phase speed of their current wavelength, so that the waves
+// raised by the wind can change length without jumping. A session starts out with the waves at rest.
+pub fn advance_waves(mut wave: ResMut<Wave>, clock: Res<SimulationClock>) {
+    if clock.frame <= 1 {
+        wave.phases = default();
+    }
+
+    wave.advance(clock.delta_seconds);
+}
+
+pub fn sync_shader_waves(wave: Res<Wave>, mut materials: ResMut<Assets<StandardOceanMaterial>>) {
     for (_, material) in materials.iter_mut() {
-        material.extension.rollback_time.elapsed_seconds = time.elapsed_seconds();
+        material.extension.settings.waves = wave.configs;
+        material.extension.wave_phases.phases = Vec4::from_array(wave.phases);
     }
 }
 
 pub fn update_buoy_water_height(
     mut buoy_query: Query<(&GlobalTransform, &mut Buoy), With<Rollback>>,
     wave: Res<Wave>,
-    time: Res<Time>,
 ) {
-    let elapsed_time = time.elapsed_seconds();
     for (global_transform, mut buoy) in &mut buoy_query {
-        buoy.water_height = wave.height(global_transform.translation(), wave.configs, elapsed_time);
+        buoy.water_height = wave.height(global_transform.translation(), wave.configs, wave.phases);
+    }
+}
+
+pub fn update_hull_water_heights(
+    mut hull_query: Query<(&GlobalTransform, &mut HullBuoyancy), With<Rollback>>,
+    wave: Res<Wave>,
+) {
+    for (global_transform, mut hull_buoyancy) in &mut hull_query {
+        let hull_buoyancy = &mut *hull_buoyancy;
+        for (column, water_height) in hull_buoyancy
+            .columns
+            .iter()
+            .zip(&mut hull_buoyancy.water_heights)
+        {
+            let column_bottom =
+                global_transform.transform_point(Vec3::new(column.x, column.bottom, column.z));
+            *water_height = wave.height(column_bottom, wave.configs, wave.phases);
+        }
     }
 }
 
 pub fn update_water_drag(
     mut ship_query: Query<(&GlobalTransform, &mut LinearDrag, &mut AngularDrag), With<Rollback>>,
     wave: Res<Wave>,
-    time: Res<Time>,
 ) {
-    let elapsed_time = time.elapsed_seconds();
     for (global_transform, mut linear_drag, mut angular_drag) in &mut ship_query {
-        let water_height = wave.height(global_transform.translation(), wave.configs, elapsed_time);
+        let water_height = wave.height(global_transform.translation(), wave.configs, wave.phases);
         if global_transform.translation().y < water_height {
             linear_drag.velocity_drag_coefficient = 20.;
             linear_drag.velocity_squared_drag_coefficient = 30.;
@@ -2,5 +2,7 @@ use bevy::prelude::*;
 
 #[derive(Event)]
 pub struct OrbitMotion {
+    // Index of the local player whose camera is orbited
+    pub local_player: usize,
     pub delta: Vec2,
 }
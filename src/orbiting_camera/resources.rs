@@ -3,6 +3,8 @@ use std::f32::consts::FRAC_PI_2;
 
 #[derive(Component)]
 pub struct OrbitingCamera {
+    // Index of the local player orbiting this camera
+    pub local_player: usize,
     pub radius: f32,
     pub pitch: f32,
     pub yaw: f32,
@@ -13,6 +15,7 @@ pub struct OrbitingCamera {
 impl Default for OrbitingCamera {
     fn default() -> Self {
         OrbitingCamera {
+            local_player: 0,
             radius: 10.,
             pitch: 30_f32.to_radians(),
             yaw: 0.,
@@ -1,21 +1,28 @@
-use crate::focal_point::resources::FocalPoint;
+use crate::focal_point::components::CameraFocalPoint;
 use crate::orbiting_camera::events::OrbitMotion;
 use crate::orbiting_camera::resources::OrbitingCamera;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use std::f32::consts::PI;
 
 pub fn orbit(
     window_query: Query<&Window>,
     mut orbit_event_reader: EventReader<OrbitMotion>,
-    mut orbiting_camera_query: Query<(&mut Transform, &mut OrbitingCamera)>,
-    focal_point: Res<FocalPoint>,
+    mut orbiting_camera_query: Query<(&mut Transform, &mut OrbitingCamera, &CameraFocalPoint)>,
 ) {
-    let mut orbit_move = Vec2::ZERO;
+    let mut orbit_moves: HashMap<usize, Vec2> = HashMap::new();
     for orbit_event in orbit_event_reader.read() {
-        orbit_move += orbit_event.delta;
+        *orbit_moves.entry(orbit_event.local_player).or_default() += orbit_event.delta;
     }
 
-    for (mut camera_transform, mut orbiting_camera) in &mut orbiting_camera_query {
+    for (mut camera_transform, mut orbiting_camera, camera_focal_point) in
+        &mut orbiting_camera_query
+    {
+        let orbit_move = orbit_moves
+            .get(&orbiting_camera.local_player)
+            .copied()
+            .unwrap_or_default();
+
         if orbit_move.length_squared() > 0.0 {
             let window = window_query.single();
             let window_width = window.resolution.width();
@@ -40,7 +47,7 @@ pub fn orbit(
         // parent = x and y rotation
         // child = z-offset
         let rot_matrix = Mat3::from_quat(camera_transform.rotation);
-        camera_transform.translation =
-            focal_point.0 + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, orbiting_camera.radius));
+        camera_transform.translation = camera_focal_point.translation
+            + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, orbiting_camera.radius));
     }
 }
@@ -1,25 +1,41 @@
+use crate::desync_report::ChecksumReportApp;
 use crate::physics::components::{
-    checksum_angular_velocity, checksum_bending_spring_orientation,
-    checksum_bending_spring_rest_orientation, checksum_damping, checksum_external_force,
-    checksum_linear_velocity, checksum_mass, checksum_spring_damping, checksum_spring_stiffness,
-    checksum_torque_impulse, AngularVelocity, BendingSpringOrientation,
-    BendingSpringRestOrientation, ExternalForce, LinearDamping, LinearVelocity, Mass,
-    SpringDamping, SpringStiffness, TorqueImpulse,
+    checksum_angular_damping, checksum_angular_drag, checksum_angular_velocity,
+    checksum_bending_spring_orientation, checksum_bending_spring_rest_orientation, checksum_buoy,
+    checksum_collider, checksum_damping, checksum_external_force, checksum_external_impulse,
+    checksum_external_torque, checksum_external_torque_impulse, checksum_hull_buoyancy,
+    checksum_linear_drag, checksum_linear_velocity, checksum_mass, checksum_spring_damping,
+    checksum_spring_stiffness, checksum_torque_impulse, Aerofoil, AngularDamping, AngularDrag,
+    AngularVelocity, Area, BendingSpringOrientation, BendingSpringRestOrientation, Buoy, Collider,
+    CollisionGroup, ExternalForce, ExternalImpulse, ExternalTorque, ExternalTorqueImpulse,
+    HullBuoyancy, Hydrofoil, Inertia, LinearDamping, LinearDrag, LinearVelocity, Mass, Rudder,
+    SailTrim, SpringDamping, SpringStiffness, TorqueImpulse, WindDrift,
 };
+use crate::physics::events::Collision;
 use crate::physics::resources::{AirDensity, Gravity, WaterDensity};
 use crate::physics::systems::{
-    update_aerodynamic_force, update_angular_drag_force, update_angular_velocity,
-    update_buoyant_force, update_hydrodynamic_force, update_linear_drag_force,
-    update_linear_velocity, update_orientation, update_position,
+    detect_collisions, resolve_collisions, update_aerodynamic_force, update_angular_drag_force,
+    update_angular_velocity, update_buoyant_force, update_hull_buoyant_force,
+    update_hydrodynamic_force, update_linear_drag_force, update_linear_velocity,
+    update_orientation, update_position,
 };
 use bevy::prelude::*;
 use bevy_ggrs::{GgrsApp, GgrsSchedule};
 
 pub mod bundles;
 pub mod components;
-mod resources;
+pub mod events;
+pub mod resources;
 pub mod systems;
 
+// Share of the approach speed that colliding bodies bounce back with
+pub const COLLISION_RESTITUTION: f32 = 0.3;
+
+// Share of the penetration between colliding bodies removed per frame, and the penetration left
+// alone to keep resting contacts from jittering
+pub const PENETRATION_CORRECTION: f32 = 0.2;
+pub const PENETRATION_SLOP: f32 = 0.01;
+
 pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
@@ -28,14 +44,19 @@ impl Plugin for PhysicsPlugin {
         app.insert_resource(WaterDensity::default());
         app.insert_resource(AirDensity::default());
 
+        app.add_event::<Collision>();
+
         app.add_systems(
             GgrsSchedule,
             (
                 update_aerodynamic_force,
                 update_hydrodynamic_force,
                 update_buoyant_force,
+                update_hull_buoyant_force,
                 update_linear_drag_force,
                 update_angular_drag_force,
+                detect_collisions,
+                resolve_collisions,
                 update_linear_velocity,
                 update_position,
                 update_angular_velocity,
@@ -44,26 +65,61 @@ impl Plugin for PhysicsPlugin {
                 .chain(),
         );
 
+        // Every component read or written by the systems above needs to be restored on rollback,
+        // not only the ones that change, since rolled back entities might need to be respawned
         app.rollback_component_with_copy::<LinearVelocity>();
+        app.rollback_component_with_copy::<AngularVelocity>();
         app.rollback_component_with_copy::<ExternalForce>();
+        app.rollback_component_with_copy::<ExternalTorque>();
+        app.rollback_component_with_copy::<ExternalImpulse>();
+        app.rollback_component_with_copy::<ExternalTorqueImpulse>();
         app.rollback_component_with_copy::<LinearDamping>();
+        app.rollback_component_with_copy::<AngularDamping>();
+        app.rollback_component_with_copy::<LinearDrag>();
+        app.rollback_component_with_copy::<AngularDrag>();
+        app.rollback_component_with_copy::<WindDrift>();
+        app.rollback_component_with_copy::<Mass>();
+        app.rollback_component_with_copy::<Inertia>();
+        app.rollback_component_with_copy::<Buoy>();
+        app.rollback_component_with_clone::<HullBuoyancy>();
+        app.rollback_component_with_copy::<Collider>();
+        app.rollback_component_with_copy::<CollisionGroup>();
+        app.rollback_component_with_copy::<Area>();
+        app.rollback_component_with_copy::<Aerofoil>();
+        app.rollback_component_with_copy::<Hydrofoil>();
+        app.rollback_component_with_copy::<Rudder>();
+        app.rollback_component_with_copy::<SailTrim>();
         app.rollback_component_with_copy::<SpringStiffness>();
         app.rollback_component_with_copy::<SpringDamping>();
         app.rollback_component_with_copy::<BendingSpringRestOrientation>();
         app.rollback_component_with_copy::<BendingSpringOrientation>();
         app.rollback_component_with_copy::<TorqueImpulse>();
 
-        app.checksum_component::<LinearVelocity>(checksum_linear_velocity);
-        app.checksum_component::<ExternalForce>(checksum_external_force);
-        app.checksum_component::<LinearDamping>(checksum_damping);
-        app.checksum_component::<Mass>(checksum_mass);
-        app.checksum_component::<AngularVelocity>(checksum_angular_velocity);
-        app.checksum_component::<TorqueImpulse>(checksum_torque_impulse);
-        app.checksum_component::<SpringStiffness>(checksum_spring_stiffness);
-        app.checksum_component::<SpringDamping>(checksum_spring_damping);
-        app.checksum_component::<BendingSpringRestOrientation>(
+        app.checksum_and_report_component::<LinearVelocity>(checksum_linear_velocity);
+        app.checksum_and_report_component::<ExternalForce>(checksum_external_force);
+        app.checksum_and_report_component::<ExternalTorque>(checksum_external_torque);
+        app.checksum_and_report_component::<ExternalImpulse>(checksum_external_impulse);
+        app.checksum_and_report_component::<ExternalTorqueImpulse>(
+            checksum_external_torque_impulse,
+        );
+        app.checksum_and_report_component::<LinearDamping>(checksum_damping);
+        app.checksum_and_report_component::<AngularDamping>(checksum_angular_damping);
+        app.checksum_and_report_component::<LinearDrag>(checksum_linear_drag);
+        app.checksum_and_report_component::<AngularDrag>(checksum_angular_drag);
+        app.checksum_and_report_component::<Mass>(checksum_mass);
+        app.checksum_and_report_component::<AngularVelocity>(checksum_angular_velocity);
+        app.checksum_and_report_component::<Buoy>(checksum_buoy);
+        app.checksum_and_report_component::<HullBuoyancy>(checksum_hull_buoyancy);
+        app.checksum_and_report_component::<Collider>(checksum_collider);
+        app.checksum_and_report_component_with_hash::<CollisionGroup>();
+        app.checksum_and_report_component::<TorqueImpulse>(checksum_torque_impulse);
+        app.checksum_and_report_component::<SpringStiffness>(checksum_spring_stiffness);
+        app.checksum_and_report_component::<SpringDamping>(checksum_spring_damping);
+        app.checksum_and_report_component::<BendingSpringRestOrientation>(
             checksum_bending_spring_rest_orientation,
         );
-        app.checksum_component::<BendingSpringOrientation>(checksum_bending_spring_orientation);
+        app.checksum_and_report_component::<BendingSpringOrientation>(
+            checksum_bending_spring_orientation,
+        );
     }
 }
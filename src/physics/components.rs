@@ -1,5 +1,9 @@
-use crate::utils::hash::{hash_f32_number, hash_quat, hash_vec3};
+use crate::utils::collision_detection::WorldShape;
+use crate::utils::hash::{hash_f32_number, hash_quat, hash_vec2, hash_vec3};
+use crate::utils::hull_voxelization::{voxelize_convex_hull, VoxelColumn};
 use bevy::prelude::*;
+use bevy::utils::FixedState;
+use std::hash::{BuildHasher, Hash, Hasher};
 
 #[derive(Component, Reflect, Clone, Copy, Default)]
 #[reflect(Component)]
@@ -36,6 +40,96 @@ pub struct Buoy {
     pub max_depth: f32,
 }
 
+#[derive(Reflect, Clone, Copy, Debug)]
+pub enum ColliderShape {
+    Sphere { radius: f32 },
+    Cuboid { half_extents: Vec3 },
+}
+
+impl Default for ColliderShape {
+    fn default() -> Self {
+        ColliderShape::Sphere { radius: 0.5 }
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy, Default, Debug)]
+#[reflect(Component)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    // Center of the shape in the space of the entity
+    pub center: Vec3,
+}
+
+impl Collider {
+    pub fn world_shape(&self, global_transform: &GlobalTransform) -> WorldShape {
+        let center = global_transform.transform_point(self.center);
+        match self.shape {
+            ColliderShape::Sphere { radius } => WorldShape::Sphere { center, radius },
+            ColliderShape::Cuboid { half_extents } => WorldShape::Cuboid {
+                center,
+                rotation: Mat3::from(global_transform.affine().matrix3),
+                half_extents,
+            },
+        }
+    }
+}
+
+// Colliders in the same group pass through each other, like ships and the cannon balls they fire
+#[derive(Component, Reflect, Clone, Copy, Default, Hash, PartialEq, Debug)]
+#[reflect(Component)]
+pub struct CollisionGroup(pub usize);
+
+// Buoyancy of a whole hull, filled with voxels arranged in vertical columns. Each voxel displaces
+// the water below the surface sampled at its column.
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component)]
+pub struct HullBuoyancy {
+    pub voxel_size: f32,
+    #[reflect(ignore)]
+    pub columns: Vec<VoxelColumn>,
+    // Water height sampled at the bottom of each column
+    pub water_heights: Vec<f32>,
+    // Fraction of each column taken up by water that leaked in, which no longer lifts the hull
+    pub flooding: Vec<f32>,
+    // Fraction of the displaced water that lifts the hull, lowered when the ship sinks
+    pub buoyancy_ratio: f32,
+}
+
+impl HullBuoyancy {
+    pub fn new(vertices: &[Vec3], voxel_size: f32) -> Self {
+        let columns = voxelize_convex_hull(vertices, voxel_size);
+        Self {
+            voxel_size,
+            water_heights: vec![0.; columns.len()],
+            flooding: vec![0.; columns.len()],
+            columns,
+            buoyancy_ratio: 1.,
+        }
+    }
+
+    // Voxel centers in hull space, paired with the water height and flooding of their column
+    pub fn voxels(&self) -> impl Iterator<Item = (Vec3, f32, f32)> + '_ {
+        let voxel_size = self.voxel_size;
+        self.columns
+            .iter()
+            .zip(&self.water_heights)
+            .zip(&self.flooding)
+            .flat_map(move |((column, water_height), flooding)| {
+                (0..column.voxel_count).map(move |index| {
+                    (
+                        Vec3::new(
+                            column.x,
+                            column.bottom + index as f32 * voxel_size,
+                            column.z,
+                        ),
+                        *water_height,
+                        *flooding,
+                    )
+                })
+            })
+    }
+}
+
 #[derive(Component, Reflect, Clone, Copy, Default)]
 #[reflect(Component)]
 pub struct Area(pub f32);
@@ -115,6 +209,12 @@ pub struct LinearDrag {
     pub velocity_squared_drag_coefficient: f32,
 }
 
+// Linear drag of bodies marked with this is caused by the air moving past them, which makes them
+// drift with the wind
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct WindDrift;
+
 #[derive(Component, Reflect, Clone, Copy, Default, Debug)]
 #[reflect(Component)]
 pub struct AngularDrag {
@@ -170,6 +270,62 @@ pub fn checksum_external_force(value: &ExternalForce) -> u64 {
     hash_vec3(value.0)
 }
 
+pub fn checksum_external_torque(value: &ExternalTorque) -> u64 {
+    hash_vec3(value.0)
+}
+
+pub fn checksum_external_impulse(value: &ExternalImpulse) -> u64 {
+    hash_vec3(value.0)
+}
+
+pub fn checksum_external_torque_impulse(value: &ExternalTorqueImpulse) -> u64 {
+    hash_vec3(value.0)
+}
+
+pub fn checksum_buoy(value: &Buoy) -> u64 {
+    hash_vec3(Vec3::new(value.volume, value.water_height, value.max_depth))
+}
+
+pub fn checksum_hull_buoyancy(value: &HullBuoyancy) -> u64 {
+    let mut hasher = FixedState.build_hasher();
+    hash_f32_number(value.buoyancy_ratio).hash(&mut hasher);
+    for water_height in &value.water_heights {
+        hash_f32_number(*water_height).hash(&mut hasher);
+    }
+    for flooding in &value.flooding {
+        hash_f32_number(*flooding).hash(&mut hasher);
+    }
+
+    #[allow(clippy::manual_hash_one)]
+    hasher.finish()
+}
+
+pub fn checksum_collider(value: &Collider) -> u64 {
+    let mut hasher = FixedState.build_hasher();
+    match value.shape {
+        ColliderShape::Sphere { radius } => hash_f32_number(radius).hash(&mut hasher),
+        ColliderShape::Cuboid { half_extents } => hash_vec3(half_extents).hash(&mut hasher),
+    }
+    hash_vec3(value.center).hash(&mut hasher);
+
+    #[allow(clippy::manual_hash_one)]
+    hasher.finish()
+}
+
+pub fn checksum_linear_drag(value: &LinearDrag) -> u64 {
+    hash_vec2(Vec2::new(
+        value.velocity_drag_coefficient,
+        value.velocity_squared_drag_coefficient,
+    ))
+}
+
+pub fn checksum_angular_drag(value: &AngularDrag) -> u64 {
+    hash_vec2(Vec2::new(
+        value.velocity_drag_coefficient,
+        value.velocity_squared_drag_coefficient,
+    ))
+}
+
 pub fn checksum_torque_impulse(value: &TorqueImpulse) -> u64 {
     hash_vec3(value.0)
 }
@@ -194,6 +350,10 @@ pub fn checksum_damping(value: &LinearDamping) -> u64 {
     hash_f32_number(value.0)
 }
 
+pub fn checksum_angular_damping(value: &AngularDamping) -> u64 {
+    hash_f32_number(value.0)
+}
+
 pub fn checksum_mass(value: &Mass) -> u64 {
     hash_f32_number(value.0)
 }
@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+// Sent from the rollback schedule, so frames that are simulated again after a rollback send their
+// collisions again
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Collision {
+    pub entity_a: Entity,
+    pub entity_b: Entity,
+    pub point: Vec3,
+    // Points from the first entity towards the second
+    pub normal: Vec3,
+    pub penetration_depth: f32,
+}
@@ -1,13 +1,17 @@
 use crate::physics::components::{
-    Aerofoil, AngularDamping, AngularDrag, AngularVelocity, Area, Buoy, ExternalForce,
-    ExternalImpulse, ExternalTorque, ExternalTorqueImpulse, Hydrofoil, Inertia, LinearDamping,
-    LinearDrag, LinearVelocity, Mass,
+    Aerofoil, AngularDamping, AngularDrag, AngularVelocity, Area, Buoy, Collider, CollisionGroup,
+    ExternalForce, ExternalImpulse, ExternalTorque, ExternalTorqueImpulse, HullBuoyancy, Hydrofoil,
+    Inertia, LinearDamping, LinearDrag, LinearVelocity, Mass, WindDrift,
 };
+use crate::physics::events::Collision;
 use crate::physics::resources::{AirDensity, Gravity, WaterDensity};
+use crate::physics::{COLLISION_RESTITUTION, PENETRATION_CORRECTION, PENETRATION_SLOP};
+use crate::simulation::resources::SimulationClock;
 use crate::utils::aerodynamics::scaled_lift_drag;
-use crate::wind::resources::Wind;
+use crate::utils::collision_detection::{contact, WorldShape};
+use crate::wind::resources::{Wind, WindConditions};
 use bevy::prelude::*;
-use bevy_ggrs::Rollback;
+use bevy_ggrs::{Rollback, RollbackOrdered};
 
 pub fn update_angular_velocity(
     mut physics_query: Query<
@@ -21,9 +25,9 @@ pub fn update_angular_velocity(
         ),
         With<Rollback>,
     >,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
 ) {
-    let delta_time = time.delta_seconds();
+    let delta_time = clock.delta_seconds;
     for (
         global_transform,
         inertia,
@@ -63,9 +67,9 @@ pub fn update_angular_velocity(
 
 pub fn update_orientation(
     mut physics_query: Query<(&Inertia, &AngularVelocity, &mut Transform), With<Rollback>>,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
 ) {
-    let delta_time = time.delta_seconds();
+    let delta_time = clock.delta_seconds;
     for (inertia, angular_velocity, mut transform) in &mut physics_query {
         if inertia.0 == Mat3::ZERO {
             continue;
@@ -76,6 +80,188 @@ pub fn update_orientation(
     }
 }
 
+// Sweeps the bounding spheres of all colliders along the x axis, only testing the exact shapes of
+// pairs whose spheres overlap
+pub fn detect_collisions(
+    collider_query: Query<(
+        Entity,
+        &Rollback,
+        &Collider,
+        &GlobalTransform,
+        Option<&CollisionGroup>,
+    )>,
+    rollback_ordered: Res<RollbackOrdered>,
+    mut collision_event_writer: EventWriter<Collision>,
+) {
+    let mut colliders: Vec<_> = collider_query
+        .iter()
+        .map(
+            |(entity, rollback, collider, global_transform, collision_group)| {
+                (
+                    rollback_ordered.order(*rollback),
+                    entity,
+                    collider.world_shape(global_transform),
+                    collision_group.copied(),
+                )
+            },
+        )
+        .collect();
+
+    // Query order may differ between peers, so ties are broken by the rollback order to send
+    // collisions in the same order everywhere
+    let min_x = |shape: &WorldShape| shape.center().x - shape.bounding_radius();
+    colliders.sort_by(|(order_a, _, shape_a, _), (order_b, _, shape_b, _)| {
+        min_x(shape_a)
+            .total_cmp(&min_x(shape_b))
+            .then(order_a.cmp(order_b))
+    });
+
+    for (index, (_, entity_a, shape_a, collision_group_a)) in colliders.iter().enumerate() {
+        let max_x = shape_a.center().x + shape_a.bounding_radius();
+        for (_, entity_b, shape_b, collision_group_b) in &colliders[index + 1..] {
+            if min_x(shape_b) > max_x {
+                break;
+            }
+
+            if collision_group_a.is_some() && collision_group_a == collision_group_b {
+                continue;
+            }
+
+            let bounding_distance = shape_a.bounding_radius() + shape_b.bounding_radius();
+            if shape_a.center().distance_squared(shape_b.center()) > bounding_distance.powi(2) {
+                continue;
+            }
+
+            if let Some(contact) = contact(shape_a, shape_b) {
+                collision_event_writer.send(Collision {
+                    entity_a: *entity_a,
+                    entity_b: *entity_b,
+                    point: contact.point,
+                    normal: contact.normal,
+                    penetration_depth: contact.penetration_depth,
+                });
+            }
+        }
+    }
+}
+
+// Inverse mass and world space inverse inertia, zero for bodies that can't be moved or spun
+fn inverse_mass_properties(
+    global_transform: &GlobalTransform,
+    mass: &Mass,
+    inertia: Option<&Inertia>,
+) -> (f32, Mat3) {
+    let inverse_mass = if mass.0 > 0. { 1. / mass.0 } else { 0. };
+
+    let inverse_inertia = match inertia {
+        Some(inertia) if inertia.0 != Mat3::ZERO => {
+            let rotation = Mat3::from(global_transform.affine().matrix3);
+            rotation * inertia.0.inverse() * rotation.transpose()
+        }
+        _ => Mat3::ZERO,
+    };
+
+    (inverse_mass, inverse_inertia)
+}
+
+// Pushes colliding bodies apart with an impulse at the contact point, which also removes part of
+// the penetration so that bodies don't stay stuck inside each other
+pub fn resolve_collisions(
+    mut collision_event_reader: EventReader<Collision>,
+    mut body_query: Query<
+        (
+            &GlobalTransform,
+            &Mass,
+            &LinearVelocity,
+            &mut ExternalImpulse,
+            Option<&Inertia>,
+            Option<&AngularVelocity>,
+            Option<&mut ExternalTorqueImpulse>,
+        ),
+        With<Rollback>,
+    >,
+    clock: Res<SimulationClock>,
+) {
+    for collision in collision_event_reader.read() {
+        let Ok([body_a, body_b]) =
+            body_query.get_many_mut([collision.entity_a, collision.entity_b])
+        else {
+            continue;
+        };
+
+        let (
+            global_transform_a,
+            mass_a,
+            linear_velocity_a,
+            mut external_impulse_a,
+            inertia_a,
+            angular_velocity_a,
+            external_torque_impulse_a,
+        ) = body_a;
+        let (
+            global_transform_b,
+            mass_b,
+            linear_velocity_b,
+            mut external_impulse_b,
+            inertia_b,
+            angular_velocity_b,
+            external_torque_impulse_b,
+        ) = body_b;
+
+        let (inverse_mass_a, inverse_inertia_a) =
+            inverse_mass_properties(global_transform_a, mass_a, inertia_a);
+        let (inverse_mass_b, inverse_inertia_b) =
+            inverse_mass_properties(global_transform_b, mass_b, inertia_b);
+
+        let lever_a = collision.point - global_transform_a.translation();
+        let lever_b = collision.point - global_transform_b.translation();
+
+        let point_velocity_a = linear_velocity_a.0
+            + angular_velocity_a.map_or(Vec3::ZERO, |angular_velocity| {
+                angular_velocity.0.cross(lever_a)
+            });
+        let point_velocity_b = linear_velocity_b.0
+            + angular_velocity_b.map_or(Vec3::ZERO, |angular_velocity| {
+                angular_velocity.0.cross(lever_b)
+            });
+
+        let normal = collision.normal;
+        let normal_velocity = (point_velocity_b - point_velocity_a).dot(normal);
+
+        let effective_inverse_mass = inverse_mass_a
+            + inverse_mass_b
+            + normal.dot(
+                (inverse_inertia_a * lever_a.cross(normal)).cross(lever_a)
+                    + (inverse_inertia_b * lever_b.cross(normal)).cross(lever_b),
+            );
+        if effective_inverse_mass <= 0. {
+            continue;
+        }
+
+        let separation_velocity = PENETRATION_CORRECTION
+            * (collision.penetration_depth - PENETRATION_SLOP).max(0.)
+            / clock.delta_seconds;
+        let impulse_magnitude = (-(1. + COLLISION_RESTITUTION) * normal_velocity.min(0.)
+            + separation_velocity)
+            / effective_inverse_mass;
+        if impulse_magnitude <= 0. {
+            continue;
+        }
+
+        let impulse = normal * impulse_magnitude;
+
+        external_impulse_a.0 -= impulse;
+        if let Some(mut external_torque_impulse) = external_torque_impulse_a {
+            external_torque_impulse.0 -= lever_a.cross(impulse);
+        }
+
+        external_impulse_b.0 += impulse;
+        if let Some(mut external_torque_impulse) = external_torque_impulse_b {
+            external_torque_impulse.0 += lever_b.cross(impulse);
+        }
+    }
+}
+
 pub fn update_linear_velocity(
     gravity: Res<Gravity>,
     mut physics_query: Query<
@@ -88,9 +274,9 @@ pub fn update_linear_velocity(
         ),
         With<Rollback>,
     >,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
 ) {
-    let delta_time = time.delta_seconds();
+    let delta_time = clock.delta_seconds;
     for (mass, linear_damping, mut external_force, mut external_impulse, mut velocity) in
         &mut physics_query
     {
@@ -122,9 +308,9 @@ pub fn update_linear_velocity(
 
 pub fn update_position(
     mut physics_query: Query<(&Mass, &LinearVelocity, &mut Transform), With<Rollback>>,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
 ) {
-    let delta_time = time.delta_seconds();
+    let delta_time = clock.delta_seconds;
     for (mass, velocity, mut transform) in &mut physics_query {
         if mass.0 <= 0. {
             continue;
@@ -136,14 +322,26 @@ pub fn update_position(
 }
 
 pub fn update_linear_drag_force(
-    mut physics_query: Query<(&LinearDrag, &LinearVelocity, &mut ExternalForce)>,
+    mut physics_query: Query<(
+        &LinearDrag,
+        &LinearVelocity,
+        &mut ExternalForce,
+        Has<WindDrift>,
+    )>,
+    wind: Res<Wind>,
 ) {
-    for (linear_drag, linear_velocity, mut external_force) in &mut physics_query {
-        if linear_velocity.0.length() > f32::EPSILON {
-            let speed = linear_velocity.0.length();
+    for (linear_drag, linear_velocity, mut external_force, drifts_with_wind) in &mut physics_query {
+        let velocity = if drifts_with_wind {
+            linear_velocity.0 - wind.0
+        } else {
+            linear_velocity.0
+        };
+
+        if velocity.length() > f32::EPSILON {
+            let speed = velocity.length();
             let drag_coefficient = linear_drag.velocity_drag_coefficient * speed
                 + linear_drag.velocity_squared_drag_coefficient * speed.powi(2);
-            let drag_force = linear_velocity.0.normalize() * -drag_coefficient;
+            let drag_force = velocity.normalize() * -drag_coefficient;
             external_force.0 += drag_force;
         }
     }
@@ -193,6 +391,42 @@ pub fn update_buoyant_force(
     }
 }
 
+pub fn update_hull_buoyant_force(
+    mut hull_query: Query<(
+        &HullBuoyancy,
+        &GlobalTransform,
+        &mut ExternalForce,
+        &mut ExternalTorque,
+    )>,
+    water_density: Res<WaterDensity>,
+) {
+    for (hull_buoyancy, global_transform, mut external_force, mut external_torque) in
+        &mut hull_query
+    {
+        let voxel_size = hull_buoyancy.voxel_size;
+        let voxel_volume = voxel_size.powi(3);
+        for (voxel, water_height, flooding) in hull_buoyancy.voxels() {
+            let voxel_translation = global_transform.transform_point(voxel);
+
+            // Voxels are treated as if they stayed upright, being submerged from their bottom face
+            let submerged_proportion =
+                ((water_height - voxel_translation.y) / voxel_size + 0.5).clamp(0., 1.);
+            if submerged_proportion == 0. {
+                continue;
+            }
+
+            let force = Vec3::Y
+                * submerged_proportion
+                * voxel_volume
+                * water_density.0
+                * (1. - flooding)
+                * hull_buoyancy.buoyancy_ratio;
+            external_torque.0 += (voxel_translation - global_transform.translation()).cross(force);
+            external_force.0 += force;
+        }
+    }
+}
+
 pub fn update_aerodynamic_force(
     aerofoil_query: Query<(Entity, &GlobalTransform, &Area), With<Aerofoil>>,
     mut vessel_query: Query<(
@@ -203,9 +437,26 @@ pub fn update_aerodynamic_force(
     )>,
     parent_query: Query<&Parent>,
     wind: Res<Wind>,
+    wind_conditions: Res<WindConditions>,
     air_density: Res<AirDensity>,
 ) {
+    let sail_positions: Vec<(Entity, Vec3)> = aerofoil_query
+        .iter()
+        .map(|(entity, global_transform, _)| (entity, global_transform.translation()))
+        .collect();
+
     'outer: for (aerofoil_entity, aerofoil_global_transform, area) in &aerofoil_query {
+        // Sails downwind of other sails sail in their shadow
+        let shadowing = wind_conditions.shadowing(
+            wind.0,
+            aerofoil_global_transform.translation(),
+            sail_positions
+                .iter()
+                .filter(|(entity, _)| *entity != aerofoil_entity)
+                .map(|(_, position)| *position),
+        );
+        let local_wind = wind.0 * shadowing;
+
         for parent_entity in parent_query.iter_ancestors(aerofoil_entity) {
             if let Ok((
                 vessel_global_transform,
@@ -214,7 +465,7 @@ pub fn update_aerodynamic_force(
                 mut external_torque,
             )) = vessel_query.get_mut(parent_entity)
             {
-                let relative_velocity = wind.0 - linear_velocity.0;
+                let relative_velocity = local_wind - linear_velocity.0;
                 let aerodynamic_force_multiplier =
                     0.5 * air_density.0 * relative_velocity.length().powi(2) * area.0;
                 let (mut lift, mut drag) =
@@ -1,23 +1,34 @@
 pub mod components;
+pub mod definitions;
+pub mod resources;
 pub mod systems;
 
 use crate::game_state::states::GameState;
 use crate::physics::systems::update_aerodynamic_force;
-use crate::player::components::Player;
+use crate::player::components::{FullSailArea, HullDrag, Player};
+use crate::player::resources::ShipClasses;
 use crate::player::systems::{
-    animate_flag, animate_sail_trim, animate_wheel_turn, apply_inputs, spawn_players,
-    update_hull_drag, update_rudder, update_sail_trim_ratio, update_wheel_turn_ratio,
+    animate_flag, animate_sail_trim, animate_wheel_turn, apply_inputs, prepare_flag_mesh,
+    spawn_players, update_hull_drag, update_rudder, update_sail_area, update_sail_canvas_ratio,
+    update_sail_trim_ratio, update_wheel_turn_ratio,
 };
 use bevy::prelude::*;
 use bevy_ggrs::{GgrsApp, GgrsSchedule};
 
 pub const WHEEL_TURN_ACCELERATION: f32 = 4.;
 pub const WHEEL_TURN_DAMPING: f32 = 0.1;
+// Fraction of the canvas let out or reefed per second
+pub const SAIL_CANVAS_RATE: f32 = 0.5;
+
+// Ship definitions, relative to the assets folder
+pub const SHIPYARD_FILE_NAME: &str = "shipyard.ships.ron";
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<ShipClasses>();
+
         app.add_systems(OnEnter(GameState::InGame), spawn_players);
         app.add_systems(
             GgrsSchedule,
@@ -27,19 +38,17 @@ impl Plugin for PlayerPlugin {
                 update_hull_drag,
                 update_wheel_turn_ratio,
                 update_sail_trim_ratio,
+                update_sail_canvas_ratio,
+                update_sail_area,
             )
                 .chain()
                 .before(update_aerodynamic_force),
         );
 
-        app.add_systems(
-            Update,
-            (animate_sail_trim, animate_wheel_turn, animate_flag)
-                .run_if(in_state(GameState::InGame)),
-        );
-
         // Registered all components that needs to be restored when rollback entities are restored
         app.rollback_component_with_copy::<Player>();
+        app.rollback_component_with_copy::<HullDrag>();
+        app.rollback_component_with_copy::<FullSailArea>();
         app.rollback_component_with_clone::<Sprite>();
         app.rollback_component_with_clone::<GlobalTransform>();
         app.rollback_component_with_clone::<Handle<Image>>();
@@ -51,3 +60,18 @@ impl Plugin for PlayerPlugin {
         app.rollback_component_with_clone::<Handle<Scene>>();
     }
 }
+
+// Purely visual ship animations, not needed when running the simulation headless
+pub struct PlayerAnimationPlugin;
+
+impl Plugin for PlayerAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InGame), prepare_flag_mesh);
+
+        app.add_systems(
+            Update,
+            (animate_sail_trim, animate_wheel_turn, animate_flag)
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
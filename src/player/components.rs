@@ -11,3 +11,16 @@ pub struct Wheel;
 
 #[derive(Component)]
 pub struct Flag;
+
+// Linear damping of the hull when going forward, and the higher drag when going backwards
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct HullDrag {
+    pub forward_linear_damping: f32,
+    pub backward_linear_damping: f32,
+}
+
+// Area of the force generating sail with all canvas let out, which reefing shrinks
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct FullSailArea(pub f32);
@@ -0,0 +1,282 @@
+use crate::inputs::Broadside;
+use crate::physics::components::{Collider, ColliderShape, HullBuoyancy, Inertia};
+use crate::player::SHIPYARD_FILE_NAME;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ShipPartKind {
+    Wheel,
+    // Canvas that is turned to the wind, the force generating sail is virtual
+    Sail,
+    Flag,
+}
+
+// Model placed on the hull that does not take part in the simulation
+#[derive(Deserialize, Clone, Debug)]
+pub struct ShipPart {
+    pub kind: ShipPartKind,
+    pub model: String,
+    pub translation: Vec3,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CannonDefinition {
+    pub broadside: Broadside,
+    pub translation: Vec3,
+}
+
+// Everything that sets a class of ships apart. Translations are relative to the hull model.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ShipDefinition {
+    pub name: String,
+    pub model: String,
+    // Models are lifted so that the bottom of the hull is at the ship origin, which keeps the
+    // center of mass low
+    pub model_translation: Vec3,
+    pub mass: f32,
+    // Box the inertia of the ship is approximated with
+    pub inertia_extents: Vec3,
+    pub angular_damping: f32,
+    pub forward_linear_damping: f32,
+    pub backward_linear_damping: f32,
+    // Support points of the hull collision mesh in ship space, enough to span its convex hull
+    pub collision_vertices: Vec<Vec3>,
+    // Smaller hulls need smaller voxels to keep their shape
+    pub voxel_size: f32,
+    // Area of the force generating sail with all canvas let out
+    pub sail_area: f32,
+    // Smaller hulls get smaller hydrofoils, keeping their forces in proportion to the mass
+    pub keel_area: f32,
+    pub rudder_area: f32,
+    pub rudder_translation: Vec3,
+    #[serde(default)]
+    pub parts: Vec<ShipPart>,
+    pub cannon_model: String,
+    pub muzzle_velocity: f32,
+    // Impulse each cannon kicks the ship back with, kept in proportion to the mass so that light
+    // hulls don't spin out of control firing a broadside
+    pub recoil_impulse: f32,
+    pub cannons: Vec<CannonDefinition>,
+}
+
+impl ShipDefinition {
+    pub fn inertia(&self) -> Inertia {
+        let extents = self.inertia_extents;
+        Inertia::cuboid(extents.x, extents.y, extents.z, self.mass)
+    }
+
+    // Box around the collision mesh of the hull
+    pub fn collider(&self) -> Collider {
+        let min = self
+            .collision_vertices
+            .iter()
+            .copied()
+            .fold(Vec3::INFINITY, Vec3::min);
+        let max = self
+            .collision_vertices
+            .iter()
+            .copied()
+            .fold(Vec3::NEG_INFINITY, Vec3::max);
+
+        Collider {
+            shape: ColliderShape::Cuboid {
+                half_extents: (max - min) / 2.,
+            },
+            center: (min + max) / 2.,
+        }
+    }
+
+    pub fn buoyancy(&self) -> HullBuoyancy {
+        HullBuoyancy::new(&self.collision_vertices, self.voxel_size)
+    }
+
+    fn model_file_names(&self) -> impl Iterator<Item = &String> {
+        [&self.model, &self.cannon_model]
+            .into_iter()
+            .chain(self.parts.iter().map(|part| &part.model))
+    }
+}
+
+// Ship classes players can sail, read from a RON file in the assets folder so that ships can be
+// added without touching the code
+#[derive(Asset, Resource, TypePath, Deserialize, Clone)]
+pub struct Shipyard {
+    pub ships: Vec<ShipDefinition>,
+    // Scenes of all models the ships are built from, keyed by file name. Loaded along with the
+    // shipyard, and left empty when running headless.
+    #[serde(skip)]
+    pub scene_handles: HashMap<String, Handle<Scene>>,
+    // Meshes of the flag models, which are animated by moving their vertices
+    #[serde(skip)]
+    pub flag_mesh_handles: HashMap<String, Handle<Mesh>>,
+}
+
+impl Shipyard {
+    // Reads the definitions straight from the assets folder, for running the simulation without an
+    // asset server
+    pub fn read() -> Self {
+        let path = shipyard_path();
+        let ron = std::fs::read_to_string(&path)
+            .unwrap_or_else(|error| panic!("failed to read {}: {error}", path.display()));
+
+        let shipyard: Self = ron::from_str(&ron)
+            .unwrap_or_else(|error| panic!("failed to parse {}: {error}", path.display()));
+        assert!(shipyard.has_ships(), "{} lists no ships", path.display());
+
+        shipyard
+    }
+
+    // Unknown ship classes fall back to the first ship, so a shipyard is only usable with one
+    pub fn has_ships(&self) -> bool {
+        !self.ships.is_empty()
+    }
+
+    pub fn model_file_names(&self) -> Vec<String> {
+        let mut model_file_names: Vec<String> = self
+            .ships
+            .iter()
+            .flat_map(ShipDefinition::model_file_names)
+            .cloned()
+            .collect();
+        model_file_names.sort();
+        model_file_names.dedup();
+
+        model_file_names
+    }
+
+    pub fn flag_model_file_names(&self) -> Vec<String> {
+        let mut flag_model_file_names: Vec<String> = self
+            .ships
+            .iter()
+            .flat_map(|ship| &ship.parts)
+            .filter(|part| part.kind == ShipPartKind::Flag)
+            .map(|part| part.model.clone())
+            .collect();
+        flag_model_file_names.sort();
+        flag_model_file_names.dedup();
+
+        flag_model_file_names
+    }
+
+    // Ship classes are looked up by name regardless of case, so that --hull raft picks the Raft
+    pub fn find_ship(&self, name: &str) -> Option<&ShipDefinition> {
+        self.ships
+            .iter()
+            .find(|ship| ship.name.eq_ignore_ascii_case(name))
+    }
+
+    // Unknown names fall back to the first ship, which loading the shipyard makes sure exists
+    pub fn ship(&self, name: &str) -> &ShipDefinition {
+        self.find_ship(name).unwrap_or_else(|| {
+            warn!(
+                "Unknown ship class {name}, sailing the {}",
+                self.ships[0].name
+            );
+            &self.ships[0]
+        })
+    }
+
+    pub fn scene(&self, model: &str) -> Handle<Scene> {
+        self.scene_handles.get(model).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn shipyard_path() -> PathBuf {
+    bevy::asset::io::file::FileAssetReader::get_base_path()
+        .join("assets")
+        .join(SHIPYARD_FILE_NAME)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn shipyard_path() -> PathBuf {
+    PathBuf::from("assets").join(SHIPYARD_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::hull_voxelization::bounding_plane_normals;
+
+    const TOLERANCE: f32 = 0.001;
+
+    // Vertices of the hull mesh in ship space. The hull is the first mesh of the model, which may
+    // carry other parts, like the mast of the raft, after it.
+    fn hull_mesh_vertices(ship: &ShipDefinition) -> Vec<Vec3> {
+        let path = bevy::asset::io::file::FileAssetReader::get_base_path()
+            .join("assets/models")
+            .join(&ship.model);
+        let model = gltf::Gltf::open(&path)
+            .unwrap_or_else(|error| panic!("failed to open {}: {error}", path.display()));
+
+        let node = model
+            .nodes()
+            .find(|node| node.mesh().is_some())
+            .unwrap_or_else(|| panic!("{} has no mesh", path.display()));
+        let transform = Mat4::from_cols_array_2d(&node.transform().matrix());
+
+        node.mesh()
+            .unwrap()
+            .primitives()
+            .flat_map(|primitive| {
+                let reader = primitive.reader(|buffer| match buffer.source() {
+                    gltf::buffer::Source::Bin => model.blob.as_deref(),
+                    gltf::buffer::Source::Uri(_) => None,
+                });
+                reader.read_positions().unwrap().collect::<Vec<_>>()
+            })
+            .map(|position| {
+                transform.transform_point3(Vec3::from(position)) + ship.model_translation
+            })
+            .collect()
+    }
+
+    fn support_distance(vertices: &[Vec3], normal: Vec3) -> f32 {
+        vertices
+            .iter()
+            .map(|vertex| normal.dot(*vertex))
+            .fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    // Collision vertices are copied out of the hull models, so they have to be copied again
+    // whenever a model changes
+    #[test]
+    fn collision_vertices_span_the_hull_mesh() {
+        for ship in Shipyard::read().ships {
+            let mesh_vertices = hull_mesh_vertices(&ship);
+
+            for vertex in &ship.collision_vertices {
+                assert!(
+                    mesh_vertices
+                        .iter()
+                        .any(|mesh_vertex| mesh_vertex.distance(*vertex) < TOLERANCE),
+                    "collision vertex {vertex} of the {} is not a vertex of {}",
+                    ship.name,
+                    ship.model
+                );
+            }
+
+            for normal in bounding_plane_normals() {
+                let difference = support_distance(&mesh_vertices, normal)
+                    - support_distance(&ship.collision_vertices, normal);
+                assert!(
+                    difference.abs() < TOLERANCE * normal.length(),
+                    "collision vertices of the {} miss the extent of {} towards {normal}",
+                    ship.name,
+                    ship.model
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ship_classes_are_found_regardless_of_case_under_the_shipyard_name() {
+        let shipyard = Shipyard::read();
+
+        assert_eq!(shipyard.find_ship("raft").unwrap().name, "Raft");
+        assert!(shipyard.find_ship("Galleon").is_none());
+    }
+}
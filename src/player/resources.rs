@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+// Ship class sailed by each player handle, agreed on by all peers before the session starts.
+// Handles without an entry, like all players of a local session, sail the class picked in args.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ShipClasses(pub Vec<String>);
+
+impl ShipClasses {
+    pub fn class<'a>(&'a self, handle: usize, default: &'a str) -> &'a str {
+        self.0.get(handle).map_or(default, String::as_str)
+    }
+}
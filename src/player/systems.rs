@@ -1,20 +1,22 @@
 use crate::args::resources::Args;
 use crate::artillery::components::{Artillery, ArtilleryAiming, ArtilleryReady};
-use crate::artillery::{
-    PORT_BACK_CANNON_TAG, PORT_FRONT_CANNON_TAG, STARBOARD_BACK_CANNON_TAG,
-    STARBOARD_FRONT_CANNON_TAG,
-};
-use crate::assets::resources::ModelAssets;
+use crate::artillery::{PORT_CANNON_TAG, STARBOARD_CANNON_TAG};
 use crate::connection::systems::RollbackConfig;
-use crate::controls::components::{Controls, SailTrimRatio, WheelTurnRatio};
-use crate::inputs::turn_action_from_input;
+use crate::controls::components::{Controls, SailCanvasRatio, SailTrimRatio, WheelTurnRatio};
+use crate::damage::components::{HullIntegrity, Sinking};
+use crate::inputs::{
+    accelerate_action_from_input, turn_action_from_input, Broadside, STEERING_AXIS_MAX,
+};
 use crate::physics::bundles::{ParticleBundle, SpindleBundle};
 use crate::physics::components::{
-    Aerofoil, AngularDamping, Area, Buoy, Hydrofoil, Inertia, LinearDamping, LinearVelocity, Mass,
+    Aerofoil, AngularDamping, Area, CollisionGroup, Hydrofoil, LinearDamping, LinearVelocity, Mass,
     Rudder, SailTrim,
 };
-use crate::player::components::{Flag, Player, Wheel};
-use crate::player::{WHEEL_TURN_ACCELERATION, WHEEL_TURN_DAMPING};
+use crate::player::components::{Flag, FullSailArea, HullDrag, Player, Wheel};
+use crate::player::definitions::{ShipDefinition, ShipPartKind, Shipyard};
+use crate::player::resources::ShipClasses;
+use crate::player::{SAIL_CANVAS_RATE, WHEEL_TURN_ACCELERATION, WHEEL_TURN_DAMPING};
+use crate::simulation::resources::SimulationClock;
 use crate::utils::linear_algebra::face_normal;
 use crate::wind::resources::Wind;
 use bevy::math::Vec3Swizzles;
@@ -22,184 +24,174 @@ use bevy::prelude::*;
 use bevy_ggrs::{AddRollbackCommandExtension, PlayerInputs, Rollback};
 use std::f32::consts::{E, PI};
 
+// Duplicate vertices once for the flag meshes of all ships here, which will facilitate
+// recalculating normals when animating the flags later on, even if accessing the meshes through
+// the scene assets
+pub fn prepare_flag_mesh(shipyard: Res<Shipyard>, mut assets: ResMut<Assets<Mesh>>) {
+    for flag_mesh_handle in shipyard.flag_mesh_handles.values() {
+        if let Some(flag_mesh) = assets.get_mut(flag_mesh_handle) {
+            flag_mesh.duplicate_vertices();
+        }
+    }
+}
+
 pub fn spawn_players(
     mut commands: Commands,
     args: Res<Args>,
-    model_assets: Res<ModelAssets>,
-    mut assets: ResMut<Assets<Mesh>>,
+    shipyard: Res<Shipyard>,
+    ship_classes: Res<ShipClasses>,
 ) {
     let placement_circle_radius = 5.;
     for handle in 0..args.num_players {
+        let ship_definition = shipyard.ship(ship_classes.class(handle, &args.hull));
         let placement_angle = handle as f32 / args.num_players as f32 * 2. * PI;
         let x = placement_circle_radius * placement_angle.cos();
         let z = placement_circle_radius * placement_angle.sin();
 
-        // Duplicate vertices once for flag mesh here, which will facilitate recalculating normals
-        // when animating the flag later on, even if accessing the mesh through the scene asset
-        let flag_mesh_handle = &model_assets.mesh_handles["medium_flag.glb"];
-        let flag_mesh = assets.get_mut(flag_mesh_handle).unwrap();
-        flag_mesh.duplicate_vertices();
-
-        commands
-            .spawn((
-                SpatialBundle::from_transform(
-                    Transform::from_translation(Vec3::new(x, 0., z))
-                        .with_rotation(Quat::from_rotation_y(4. * PI / 8.)),
-                ),
-                Player { handle },
-                Controls::default(),
-                WheelTurnRatio::default(),
-                SailTrimRatio::default(),
-                ArtilleryReady::default(),
-                ArtilleryAiming::default(),
-                Name::new("Ship"),
-                SpindleBundle {
-                    inertia: Inertia::cuboid(4., 3., 3., 100.),
-                    angular_damping: AngularDamping(0.6),
-                    ..default()
-                },
-                ParticleBundle {
-                    mass: Mass(100.),
-                    linear_damping: LinearDamping(0.8),
-                    ..default()
+        spawn_ship(
+            &mut commands,
+            ship_definition,
+            &shipyard,
+            handle,
+            Transform::from_translation(Vec3::new(x, 0., z))
+                .with_rotation(Quat::from_rotation_y(4. * PI / 8.)),
+        );
+    }
+}
+
+// Builds the entity hierarchy of a ship of given class for the player with given handle
+pub fn spawn_ship(
+    commands: &mut Commands,
+    ship_definition: &ShipDefinition,
+    shipyard: &Shipyard,
+    handle: usize,
+    transform: Transform,
+) {
+    commands
+        .spawn((
+            SpatialBundle::from_transform(transform),
+            Player { handle },
+            Controls::default(),
+            WheelTurnRatio::default(),
+            SailTrimRatio::default(),
+            SailCanvasRatio(1.),
+            ArtilleryReady::default(),
+            ArtilleryAiming::default(),
+            Name::new("Ship"),
+            (
+                ship_definition.buoyancy(),
+                ship_definition.collider(),
+                CollisionGroup(handle),
+                HullIntegrity::default(),
+                HullDrag {
+                    forward_linear_damping: ship_definition.forward_linear_damping,
+                    backward_linear_damping: ship_definition.backward_linear_damping,
                 },
-            ))
-            .with_children(|child_builder| {
-                for buoy_translation in [
-                    Vec3::new(1.25, 0.5, 1.25),
-                    Vec3::new(-1.25, 0.5, 1.25),
-                    Vec3::new(1.25, 0.5, -1.25),
-                    Vec3::new(-1.25, 0.5, -1.25),
-                ] {
+            ),
+            SpindleBundle {
+                inertia: ship_definition.inertia(),
+                angular_damping: AngularDamping(ship_definition.angular_damping),
+                ..default()
+            },
+            ParticleBundle {
+                mass: Mass(ship_definition.mass),
+                linear_damping: LinearDamping(ship_definition.forward_linear_damping),
+                ..default()
+            },
+        ))
+        .with_children(|child_builder| {
+            child_builder
+                .spawn((
+                    SceneBundle {
+                        scene: shipyard.scene(&ship_definition.model),
+                        transform: Transform::from_translation(ship_definition.model_translation),
+                        ..default()
+                    },
+                    Name::new("Hull"),
+                ))
+                .add_rollback()
+                .with_children(|child_builder| {
+                    for part in &ship_definition.parts {
+                        let mut part_entity = child_builder.spawn(SceneBundle {
+                            scene: shipyard.scene(&part.model),
+                            transform: Transform::from_translation(part.translation),
+                            ..default()
+                        });
+
+                        match part.kind {
+                            ShipPartKind::Wheel => part_entity.insert((Wheel, Name::new("Wheel"))),
+                            ShipPartKind::Sail => part_entity.insert((SailTrim, Name::new("Sail"))),
+                            ShipPartKind::Flag => part_entity.insert((Flag, Name::new("Flag"))),
+                        };
+
+                        part_entity.add_rollback();
+                    }
+
+                    // Place the force generating sail in center of gravity so that we don't
+                    // generate any torque, which messes things up. Same with keel.
+                    child_builder
+                        .spawn((
+                            TransformBundle::from_transform(Transform::from_rotation(
+                                Quat::from_rotation_y(PI / 4.),
+                            )),
+                            Name::new("Virtual sail"),
+                            Area(ship_definition.sail_area),
+                            FullSailArea(ship_definition.sail_area),
+                            Aerofoil,
+                            SailTrim,
+                        ))
+                        .add_rollback();
+
+                    child_builder
+                        .spawn((
+                            TransformBundle::default(),
+                            Name::new("Keel"),
+                            Area(ship_definition.keel_area),
+                            Hydrofoil,
+                        ))
+                        .add_rollback();
+
                     child_builder
                         .spawn((
                             TransformBundle::from_transform(Transform::from_translation(
-                                buoy_translation,
+                                ship_definition.rudder_translation,
                             )),
-                            Buoy {
-                                volume: 0.75,
-                                max_depth: 0.5,
-                                ..default()
-                            },
+                            Name::new("Rudder"),
+                            Area(ship_definition.rudder_area),
+                            Rudder,
+                            Hydrofoil,
                         ))
                         .add_rollback();
-                }
 
-                child_builder
-                    .spawn((
-                        SceneBundle {
-                            scene: model_assets.scene_handles["medium_hull.glb"].clone(),
-                            transform: Transform::from_xyz(0., 0., 0.),
-                            ..default()
-                        },
-                        Name::new("Hull"),
-                    ))
-                    .add_rollback()
-                    .with_children(|child_builder| {
-                        child_builder
-                            .spawn((
-                                SceneBundle {
-                                    scene: model_assets.scene_handles["medium_helm.glb"].clone(),
-                                    transform: Transform::from_xyz(0., 5.5806, -1.0694),
-                                    ..default()
-                                },
-                                Wheel,
-                                Name::new("Wheel"),
-                            ))
-                            .add_rollback();
+                    for cannon in &ship_definition.cannons {
+                        let (cannon_y_rotation, name) = match cannon.broadside {
+                            Broadside::Port => (PI, PORT_CANNON_TAG),
+                            Broadside::Starboard => (0., STARBOARD_CANNON_TAG),
+                        };
 
                         child_builder
                             .spawn((
                                 SceneBundle {
-                                    scene: model_assets.scene_handles["medium_pirate_sail.glb"]
-                                        .clone(),
-                                    transform: Transform::from_xyz(0., 2.3248, 1.3574),
+                                    scene: shipyard.scene(&ship_definition.cannon_model),
+                                    transform: Transform::from_translation(cannon.translation)
+                                        .with_rotation(Quat::from_rotation_y(cannon_y_rotation)),
                                     ..default()
                                 },
-                                Name::new("Sail"),
-                                SailTrim,
-                            ))
-                            .add_rollback();
-
-                        // Place the force generating sail in center of gravity so that we don't
-                        // generate any torque, which messes things up. Same with keel.
-                        child_builder
-                            .spawn((
-                                TransformBundle::from_transform(Transform::from_rotation(
-                                    Quat::from_rotation_y(PI / 4.),
-                                )),
-                                Name::new("Virtual sail"),
-                                Area(8.),
-                                Aerofoil,
-                                SailTrim,
-                            ))
-                            .add_rollback();
-
-                        child_builder
-                            .spawn((
-                                TransformBundle::default(),
-                                Name::new("Keel"),
-                                Area(1.),
-                                Hydrofoil,
-                            ))
-                            .add_rollback();
-
-                        child_builder
-                            .spawn((
-                                TransformBundle::from_transform(Transform::from_xyz(0., -1., -2.)),
-                                Name::new("Rudder"),
-                                Area(0.05),
-                                Rudder,
-                                Hydrofoil,
-                            ))
-                            .add_rollback();
-
-                        child_builder
-                            .spawn((
-                                SceneBundle {
-                                    scene: model_assets.scene_handles["medium_flag.glb"].clone(),
-                                    transform: Transform::from_xyz(0., 9.38793, 1.35834),
+                                Artillery {
+                                    muzzle_velocity: ship_definition.muzzle_velocity,
+                                    recoil_impulse: ship_definition.recoil_impulse,
+                                    broadside: cannon.broadside,
                                     ..default()
                                 },
-                                Flag,
-                                Name::new("Flag"),
+                                AnimationPlayer::default(),
+                                Name::new(name),
                             ))
                             .add_rollback();
-
-                        #[rustfmt::skip]
-                            let cannons = [
-                            ([1.1769, 1.4593, -0.5485], PI, PORT_BACK_CANNON_TAG),
-                            ([1.13846, 1.54822, 1.54781], PI, PORT_FRONT_CANNON_TAG),
-                            ([-1.1769, 1.4593, -0.5485], 0., STARBOARD_BACK_CANNON_TAG),
-                            ([-1.13846, 1.54822, 1.54781], 0., STARBOARD_FRONT_CANNON_TAG),
-                        ];
-
-                        for (cannon_transform, cannon_y_rotation, name) in cannons {
-                            child_builder
-                                .spawn((
-                                    SceneBundle {
-                                        scene: model_assets.scene_handles["medium_canon.glb"]
-                                            .clone(),
-                                        transform: Transform::from_translation(Vec3::from_array(
-                                            cannon_transform,
-                                        ))
-                                        .with_rotation(Quat::from_rotation_y(cannon_y_rotation)),
-                                        ..default()
-                                    },
-                                    Artillery {
-                                        muzzle_velocity: 24.,
-                                        ..default()
-                                    },
-                                    AnimationPlayer::default(),
-                                    Name::new(name),
-                                ))
-                                .add_rollback();
-                        }
-                    });
-            })
-            // Add a Rollback component with a unique id
-            .add_rollback();
-    }
+                    }
+                });
+        })
+        // Add a Rollback component with a unique id
+        .add_rollback();
 }
 
 const AMPLITUDE: f32 = 0.4;
@@ -280,69 +272,91 @@ pub fn animate_flag(
 }
 
 pub fn animate_wheel_turn(
-    player_query: Query<&WheelTurnRatio, With<Rollback>>,
+    player_query: Query<(Entity, &WheelTurnRatio), With<Rollback>>,
+    children_query: Query<&Children>,
     mut helm_query: Query<&mut Transform, With<Wheel>>,
 ) {
-    for wheel_turn_ratio in &player_query {
-        for mut transform in &mut helm_query {
-            transform.rotation = Quat::from_rotation_z(wheel_turn_ratio.0 * 4.);
+    for (ship_entity, wheel_turn_ratio) in &player_query {
+        for descendant in children_query.iter_descendants(ship_entity) {
+            if let Ok(mut transform) = helm_query.get_mut(descendant) {
+                transform.rotation = Quat::from_rotation_z(wheel_turn_ratio.0 * 4.);
+            }
         }
     }
 }
 
+// Each ship only turns its own rudder
 pub fn update_rudder(
-    player_query: Query<&WheelTurnRatio, With<Rollback>>,
+    player_query: Query<(Entity, &WheelTurnRatio), With<Rollback>>,
+    children_query: Query<&Children>,
     mut rudder_query: Query<&mut Transform, With<Rudder>>,
 ) {
-    for wheel_turn_ratio in &player_query {
-        for mut transform in &mut rudder_query {
-            transform.rotation = Quat::from_rotation_y(wheel_turn_ratio.0 * PI / 8.);
+    for (ship_entity, wheel_turn_ratio) in &player_query {
+        for descendant in children_query.iter_descendants(ship_entity) {
+            if let Ok(mut transform) = rudder_query.get_mut(descendant) {
+                transform.rotation = Quat::from_rotation_y(wheel_turn_ratio.0 * PI / 8.);
+            }
         }
     }
 }
 
 pub fn animate_sail_trim(
-    player_query: Query<&SailTrimRatio, With<Rollback>>,
+    player_query: Query<(Entity, &SailTrimRatio), With<Rollback>>,
+    children_query: Query<&Children>,
     mut sail_query: Query<&mut Transform, With<SailTrim>>,
 ) {
-    for sail_trim_ratio in &player_query {
-        for mut transform in &mut sail_query {
-            transform.rotation = Quat::from_rotation_y(sail_trim_ratio.0 * PI / 4.);
+    for (ship_entity, sail_trim_ratio) in &player_query {
+        for descendant in children_query.iter_descendants(ship_entity) {
+            if let Ok(mut transform) = sail_query.get_mut(descendant) {
+                transform.rotation = Quat::from_rotation_y(sail_trim_ratio.0 * PI / 4.);
+            }
         }
     }
 }
 
 pub fn update_hull_drag(
-    mut player_query: Query<(&mut LinearDamping, &GlobalTransform, &LinearVelocity)>,
+    mut player_query: Query<(
+        &mut LinearDamping,
+        &HullDrag,
+        &GlobalTransform,
+        &LinearVelocity,
+    )>,
 ) {
-    for (mut linear_damping, global_transform, linear_velocity) in &mut player_query {
+    for (mut linear_damping, hull_drag, global_transform, linear_velocity) in &mut player_query {
         if global_transform.back().dot(linear_velocity.0) > 0. {
             // Going forward
-            linear_damping.0 = 0.8;
+            linear_damping.0 = hull_drag.forward_linear_damping;
         } else {
             // Going backwards
-            linear_damping.0 = 0.6;
+            linear_damping.0 = hull_drag.backward_linear_damping;
         }
     }
 }
 
 pub fn apply_inputs(
-    mut player_query: Query<(&mut Controls, &Player), With<Rollback>>,
+    mut player_query: Query<(&mut Controls, &Player, Has<Sinking>), With<Rollback>>,
     inputs: Res<PlayerInputs<RollbackConfig>>,
 ) {
-    for (mut controls, player) in &mut player_query {
+    for (mut controls, player, is_sinking) in &mut player_query {
+        // Crews of sinking ships abandon the helm
+        if is_sinking {
+            *controls = Controls::default();
+            continue;
+        }
+
         controls.turn_action = turn_action_from_input(inputs[player.handle]);
-        controls.accelerate_action = 1; // Always(?) sail full speed ahead
+        controls.accelerate_action = accelerate_action_from_input(inputs[player.handle]);
     }
 }
 
 pub fn update_wheel_turn_ratio(
     mut player_query: Query<(&mut WheelTurnRatio, &Controls), With<Rollback>>,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
 ) {
-    let delta_time = time.delta_seconds();
+    let delta_time = clock.delta_seconds;
     for (mut wheel_turn_ratio, controls) in &mut player_query {
-        wheel_turn_ratio.0 += controls.turn_action as f32 * WHEEL_TURN_ACCELERATION * delta_time;
+        let turn = controls.turn_action as f32 / STEERING_AXIS_MAX as f32;
+        wheel_turn_ratio.0 += turn * WHEEL_TURN_ACCELERATION * delta_time;
         wheel_turn_ratio.0 *= WHEEL_TURN_DAMPING.powf(delta_time);
         wheel_turn_ratio.0 = wheel_turn_ratio.0.clamp(-1., 1.);
     }
@@ -361,3 +375,29 @@ pub fn update_sail_trim_ratio(
         sail_trim_ratio.0 = trim_ratio;
     }
 }
+
+pub fn update_sail_canvas_ratio(
+    mut player_query: Query<(&mut SailCanvasRatio, &Controls), With<Rollback>>,
+    clock: Res<SimulationClock>,
+) {
+    let delta_time = clock.delta_seconds;
+    for (mut sail_canvas_ratio, controls) in &mut player_query {
+        sail_canvas_ratio.0 += controls.accelerate_action as f32 * SAIL_CANVAS_RATE * delta_time;
+        sail_canvas_ratio.0 = sail_canvas_ratio.0.clamp(0., 1.);
+    }
+}
+
+// Reefing shrinks the area of the force generating sail of each ship
+pub fn update_sail_area(
+    player_query: Query<(Entity, &SailCanvasRatio), With<Rollback>>,
+    children_query: Query<&Children>,
+    mut sail_query: Query<(&mut Area, &FullSailArea), With<Aerofoil>>,
+) {
+    for (ship_entity, sail_canvas_ratio) in &player_query {
+        for descendant in children_query.iter_descendants(ship_entity) {
+            if let Ok((mut area, full_sail_area)) = sail_query.get_mut(descendant) {
+                area.0 = full_sail_area.0 * sail_canvas_ratio.0;
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+mod components;
+mod systems;
+
+use crate::game_state::states::GameState;
+use crate::inputs::resources::InputBindings;
+use crate::post_match::systems::{
+    despawn_match, despawn_post_match_menu, end_match_on_key, handle_post_match_interactions,
+    spawn_post_match_menu,
+};
+use bevy::prelude::*;
+
+pub const POST_MATCH_BUTTON_NORMAL: Color = Color::rgb(0.9, 0.45, 0.21);
+pub const POST_MATCH_BUTTON_HOVER: Color = Color::rgb(0.87, 0.36, 0.18);
+
+// Ends matches and offers a rematch in the same room, or leaving back to the main menu
+pub struct PostMatchPlugin;
+
+impl Plugin for PostMatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            end_match_on_key
+                .run_if(in_state(GameState::InGame).and_then(resource_exists::<InputBindings>)),
+        );
+
+        // Tear down everything the match was simulated with, so that matchmaking can start over
+        app.add_systems(OnExit(GameState::InGame), despawn_match);
+
+        app.add_systems(OnEnter(GameState::PostMatch), spawn_post_match_menu)
+            .add_systems(OnExit(GameState::PostMatch), despawn_post_match_menu)
+            .add_systems(
+                Update,
+                handle_post_match_interactions.run_if(in_state(GameState::PostMatch)),
+            );
+    }
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct PostMatchMenuLayout;
+
+#[derive(Component)]
+pub enum PostMatchButton {
+    Rematch,
+    Leave,
+}
@@ -0,0 +1,133 @@
+use crate::artillery::components::Projectile;
+use crate::assets::resources::FontAssets;
+use crate::camera::resources::MainCamera;
+use crate::connection::systems::RollbackConfig;
+use crate::game_state::states::GameState;
+use crate::inputs::resources::{InputAction, InputBindings};
+use crate::player::components::Player;
+use crate::post_match::components::{PostMatchButton, PostMatchMenuLayout};
+use crate::post_match::{POST_MATCH_BUTTON_HOVER, POST_MATCH_BUTTON_NORMAL};
+use bevy::prelude::*;
+use bevy_ggrs::Session;
+use bevy_matchbox::prelude::*;
+
+// Read from the keys directly rather than from the inputs sent to peers, so that ending the match
+// is never rolled back
+pub fn end_match_on_key(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if bindings.just_pressed(&keys, InputAction::EndMatch) {
+        next_state.set(GameState::PostMatch);
+    }
+}
+
+pub fn despawn_match(
+    mut commands: Commands,
+    match_query: Query<Entity, Or<(With<Player>, With<Projectile>)>>,
+) {
+    // Closing the socket leaves the room, which lets remaining peers see us disconnect
+    commands.remove_resource::<Session<RollbackConfig>>();
+    commands.remove_resource::<MatchboxSocket<MultipleChannels>>();
+
+    for entity in &match_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn spawn_post_match_menu(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    main_camera: Res<MainCamera>,
+) {
+    let text_style = TextStyle {
+        font: font_assets.font_handles["the-bomb-regular.otf"].clone(),
+        font_size: 40.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            // Seems to be required in dev builds since using editor plugin results in multiple
+            // cameras
+            TargetCamera(main_camera.id),
+            PostMatchMenuLayout,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(16.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|child_builder| {
+            child_builder.spawn(TextBundle::from_section("Match Over", text_style.clone()));
+
+            for (button, label) in [
+                (PostMatchButton::Rematch, "Rematch"),
+                (PostMatchButton::Leave, "Leave"),
+            ] {
+                child_builder
+                    .spawn((
+                        button,
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(320.0),
+                                height: Val::Px(64.0),
+                                border: UiRect::all(Val::Px(8.0)),
+                                // horizontally center child text
+                                justify_content: JustifyContent::Center,
+                                // vertically center child text
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            border_color: BorderColor(Color::WHITE),
+                            background_color: POST_MATCH_BUTTON_NORMAL.into(),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(label, text_style.clone()));
+                    });
+            }
+        });
+}
+
+pub fn despawn_post_match_menu(
+    mut commands: Commands,
+    post_match_menu_query: Query<Entity, With<PostMatchMenuLayout>>,
+) {
+    for entity in &post_match_menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn handle_post_match_interactions(
+    mut button_query: Query<
+        (&Interaction, &PostMatchButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button, mut background_color) in &mut button_query {
+        match *interaction {
+            Interaction::Pressed => match button {
+                // Matchmaking again with unchanged args joins the same room
+                PostMatchButton::Rematch => next_state.set(GameState::Matchmaking),
+                PostMatchButton::Leave => next_state.set(GameState::SplashScreen),
+            },
+            Interaction::Hovered => {
+                *background_color = POST_MATCH_BUTTON_HOVER.into();
+            }
+            Interaction::None => {
+                *background_color = POST_MATCH_BUTTON_NORMAL.into();
+            }
+        }
+    }
+}
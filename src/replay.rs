@@ -0,0 +1,47 @@
+pub mod resources;
+pub mod systems;
+
+use crate::args::run_conditions::{record_replay_mode, replay_mode};
+use crate::connection::systems::RollbackConfig;
+use crate::game_state::states::GameState;
+use crate::replay::resources::InputRecording;
+use crate::replay::systems::{
+    load_replay, read_scripted_inputs, record_inputs, start_input_recording, track_confirmed_frame,
+    write_replay,
+};
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy_ggrs::{GgrsSchedule, ReadInputs, Session};
+
+// Records the inputs of all players into a replay file, or plays a replay file back instead of
+// reading local inputs
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        // Replaces args and environment before anything is spawned from them
+        app.add_systems(PreStartup, load_replay.run_if(replay_mode));
+        app.add_systems(ReadInputs, read_scripted_inputs.run_if(replay_mode));
+
+        app.add_systems(Startup, start_input_recording.run_if(record_replay_mode));
+        app.add_systems(
+            GgrsSchedule,
+            record_inputs.run_if(resource_exists::<InputRecording>),
+        );
+        app.add_systems(
+            Update,
+            track_confirmed_frame.run_if(
+                resource_exists::<InputRecording>
+                    .and_then(resource_exists::<Session<RollbackConfig>>),
+            ),
+        );
+        app.add_systems(
+            OnExit(GameState::InGame),
+            write_replay.run_if(resource_exists::<InputRecording>),
+        );
+        app.add_systems(
+            Last,
+            write_replay.run_if(on_event::<AppExit>().and_then(resource_exists::<InputRecording>)),
+        );
+    }
+}
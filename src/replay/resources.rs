@@ -0,0 +1,216 @@
+use crate::args::resources::Args;
+use crate::inputs::PlayerInput;
+use crate::ocean::resources::Wave;
+use crate::player::resources::ShipClasses;
+use crate::wind::resources::{WindConditions, WindVariation};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Resource, Default)]
+pub struct ScriptedInputs {
+    // One input per frame for each player handle. Frames beyond the end of the script read as no
+    // input.
+    pub inputs: Vec<Vec<PlayerInput>>,
+    pub frame: usize,
+}
+
+// Inputs of every player, indexed by handle and then by frame. Frames that are resimulated after a
+// rollback overwrite their predicted inputs with the confirmed ones.
+#[derive(Resource)]
+pub struct InputRecording {
+    pub environment: ReplayEnvironment,
+    pub args: Args,
+    pub inputs: Vec<Vec<PlayerInput>>,
+    // Number of frames for which the inputs of all players are confirmed, none when every recorded
+    // input is, as in local sessions
+    pub confirmed_frames: Option<usize>,
+}
+
+impl InputRecording {
+    pub fn record(&mut self, frame: usize, handle: usize, input: PlayerInput) {
+        if self.inputs.len() <= handle {
+            self.inputs.resize_with(handle + 1, Vec::new);
+        }
+
+        let player_inputs = &mut self.inputs[handle];
+        if player_inputs.len() <= frame {
+            player_inputs.resize(frame + 1, PlayerInput::default());
+        }
+
+        player_inputs[frame] = input;
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct ReplayEnvironment {
+    pub wave_time_scale: f32,
+    pub wave_sample_count: u8,
+    pub wave_configs: [[f32; 4]; 4],
+    // Mean wind
+    pub wind: [f32; 3],
+    #[serde(default)]
+    pub wind_variation: WindVariation,
+}
+
+impl ReplayEnvironment {
+    pub fn new(wave: &Wave, wind_conditions: &WindConditions) -> Self {
+        Self {
+            wave_time_scale: wave.time_scale,
+            wave_sample_count: wave.sample_count,
+            wave_configs: wave.configs.map(|config| config.to_array()),
+            wind: wind_conditions.mean.to_array(),
+            wind_variation: wind_conditions.variation,
+        }
+    }
+
+    pub fn apply(&self, wave: &mut Wave, wind_conditions: &mut WindConditions) {
+        wave.time_scale = self.wave_time_scale;
+        wave.sample_count = self.wave_sample_count;
+        wave.configs = self.wave_configs.map(Vec4::from_array);
+        wind_conditions.mean = Vec3::from_array(self.wind);
+        wind_conditions.variation = self.wind_variation;
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    pub args: Args,
+    pub environment: ReplayEnvironment,
+    // Ship class of each player handle, empty for local sessions where all players sail the class
+    // in args
+    #[serde(default)]
+    pub ship_classes: Vec<String>,
+    // Run length encoded inputs for each player handle, as pairs of input and number of frames
+    pub inputs: Vec<Vec<(PlayerInput, u32)>>,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read replay {}: {error}", path.display()))?;
+
+        serde_json::from_str(&json)
+            .map_err(|error| format!("Failed to parse replay {}: {error}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string(self).expect("failed to serialize replay");
+
+        fs::write(path, json)
+    }
+
+    pub fn from_recording(recording: &InputRecording, ship_classes: &ShipClasses) -> Self {
+        let inputs = recording
+            .inputs
+            .iter()
+            .map(|player_inputs| {
+                // Predicted inputs at the end of an online match are left out
+                let confirmed_frames = recording.confirmed_frames.unwrap_or(player_inputs.len());
+                let player_inputs = &player_inputs[..confirmed_frames.min(player_inputs.len())];
+
+                let mut runs: Vec<(PlayerInput, u32)> = Vec::new();
+                for input in player_inputs {
+                    match runs.last_mut() {
+                        Some((run_input, frames)) if run_input == input => *frames += 1,
+                        _ => runs.push((*input, 1)),
+                    }
+                }
+                runs
+            })
+            .collect();
+
+        Self {
+            args: recording.args.clone(),
+            environment: recording.environment,
+            ship_classes: ship_classes.0.clone(),
+            inputs,
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.inputs
+            .iter()
+            .map(|runs| runs.iter().map(|(_, frames)| *frames as usize).sum())
+            .max()
+            .unwrap_or_default()
+    }
+
+    // Decodes the inputs into one input per frame for each player handle
+    pub fn scripted_inputs(&self) -> ScriptedInputs {
+        ScriptedInputs {
+            inputs: self
+                .inputs
+                .iter()
+                .map(|runs| {
+                    runs.iter()
+                        .flat_map(|(input, frames)| {
+                            std::iter::repeat(*input).take(*frames as usize)
+                        })
+                        .collect()
+                })
+                .collect(),
+            frame: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::{INPUT_FIRE, INPUT_UP};
+    use clap::Parser;
+
+    fn recording(inputs: Vec<Vec<PlayerInput>>, confirmed_frames: Option<usize>) -> InputRecording {
+        InputRecording {
+            environment: ReplayEnvironment::new(
+                &Wave::default(),
+                &WindConditions {
+                    mean: Vec3::X,
+                    variation: default(),
+                },
+            ),
+            args: Args::parse_from(["pirate-sea-jam"]),
+            inputs,
+            confirmed_frames,
+        }
+    }
+
+    #[test]
+    fn run_length_encoding_round_trips_recorded_inputs() {
+        let up = PlayerInput {
+            buttons: INPUT_UP,
+            steering: 0,
+        };
+        let fire_steering = PlayerInput {
+            buttons: INPUT_FIRE,
+            steering: -64,
+        };
+        let inputs = vec![
+            vec![up, up, up, fire_steering, PlayerInput::default(), up],
+            vec![PlayerInput::default(); 4],
+        ];
+
+        let replay = Replay::from_recording(&recording(inputs.clone(), None), &default());
+
+        assert_eq!(replay.inputs[0].len(), 4);
+        assert_eq!(replay.inputs[1], vec![(PlayerInput::default(), 4)]);
+        assert_eq!(replay.frame_count(), 6);
+        assert_eq!(replay.scripted_inputs().inputs, inputs);
+    }
+
+    #[test]
+    fn unconfirmed_frames_are_left_out_of_the_replay() {
+        let up = PlayerInput {
+            buttons: INPUT_UP,
+            steering: 0,
+        };
+        let inputs = vec![vec![up; 10], vec![PlayerInput::default(); 10]];
+
+        let replay = Replay::from_recording(&recording(inputs, Some(7)), &default());
+
+        assert_eq!(replay.frame_count(), 7);
+        assert_eq!(replay.scripted_inputs().inputs[0], vec![up; 7]);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::args::resources::Args;
+use crate::connection::systems::RollbackConfig;
+use crate::ocean::resources::Wave;
+use crate::player::resources::ShipClasses;
+use crate::replay::resources::{InputRecording, Replay, ReplayEnvironment, ScriptedInputs};
+use crate::wind::resources::WindConditions;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_ggrs::{LocalInputs, LocalPlayers, PlayerInputs, RollbackFrameCount, Session};
+
+pub fn read_scripted_inputs(
+    mut commands: Commands,
+    mut scripted_inputs: ResMut<ScriptedInputs>,
+    local_players: Res<LocalPlayers>,
+) {
+    let mut local_inputs = HashMap::new();
+
+    for handle in &local_players.0 {
+        let input = scripted_inputs
+            .inputs
+            .get(*handle)
+            .and_then(|inputs| inputs.get(scripted_inputs.frame))
+            .copied()
+            .unwrap_or_default();
+
+        local_inputs.insert(*handle, input);
+    }
+
+    scripted_inputs.frame += 1;
+
+    commands.insert_resource(LocalInputs::<RollbackConfig>(local_inputs));
+}
+
+pub fn load_replay(
+    mut commands: Commands,
+    mut args: ResMut<Args>,
+    mut wave: ResMut<Wave>,
+    mut wind_conditions: ResMut<WindConditions>,
+) {
+    let Some(path) = args.replay.clone() else {
+        return;
+    };
+
+    info!("Playing back replay {}", path.display());
+    let replay = match Replay::load(&path) {
+        Ok(replay) => replay,
+        Err(error) => {
+            error!("{error}, playing without it");
+            args.replay = None;
+            return;
+        }
+    };
+
+    // Replays are played back locally, every player reading from the replay
+    args.sync_test = true;
+    args.num_players = replay.args.num_players;
+    args.hull = replay.args.hull.clone();
+    args.wind_driven_waves = replay.args.wind_driven_waves;
+
+    replay.environment.apply(&mut wave, &mut wind_conditions);
+    commands.insert_resource(ShipClasses(replay.ship_classes.clone()));
+    commands.insert_resource(replay.scripted_inputs());
+}
+
+pub fn start_input_recording(
+    mut commands: Commands,
+    args: Res<Args>,
+    wave: Res<Wave>,
+    wind_conditions: Res<WindConditions>,
+) {
+    commands.insert_resource(InputRecording {
+        environment: ReplayEnvironment::new(&wave, &wind_conditions),
+        args: args.clone(),
+        inputs: Vec::new(),
+        confirmed_frames: None,
+    });
+}
+
+pub fn record_inputs(
+    mut recording: ResMut<InputRecording>,
+    inputs: Res<PlayerInputs<RollbackConfig>>,
+    frame_count: Res<RollbackFrameCount>,
+) {
+    // The frame count has already been advanced to the frame being simulated
+    let frame = frame_count.0 as usize - 1;
+
+    for (handle, (input, _)) in inputs.iter().enumerate() {
+        recording.record(frame, handle, *input);
+    }
+}
+
+// Online sessions predict the inputs of remote players ahead of the last confirmed frame, which
+// must not end up in the replay
+pub fn track_confirmed_frame(
+    mut recording: ResMut<InputRecording>,
+    session: Res<Session<RollbackConfig>>,
+) {
+    if let Session::P2P(session) = session.as_ref() {
+        recording.confirmed_frames = Some((session.confirmed_frame() + 1).max(0) as usize);
+    }
+}
+
+pub fn write_replay(
+    mut recording: ResMut<InputRecording>,
+    args: Res<Args>,
+    ship_classes: Res<ShipClasses>,
+) {
+    let Some(path) = &args.record_replay else {
+        return;
+    };
+
+    // Already written when the match ended
+    if recording.inputs.is_empty() {
+        return;
+    }
+
+    let replay = Replay::from_recording(&recording, &ship_classes);
+
+    match replay.save(path) {
+        Ok(()) => info!(
+            "Recorded {} frames into replay {}",
+            replay.frame_count(),
+            path.display()
+        ),
+        Err(error) => error!("Failed to write replay {}: {error}", path.display()),
+    }
+
+    // A rematch starts recording from the first frame again
+    recording.inputs.clear();
+    recording.confirmed_frames = None;
+}
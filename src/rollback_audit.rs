@@ -0,0 +1,32 @@
+mod resources;
+mod systems;
+
+use crate::rollback_audit::resources::{RegisteredRollbackComponents, RollbackAuditTick};
+use crate::rollback_audit::systems::{
+    assert_changed_components_registered, collect_registered_rollback_components,
+    record_rollback_audit_tick,
+};
+use bevy::prelude::*;
+use bevy_ggrs::{AdvanceWorld, AdvanceWorldSet};
+
+// Debug check that every component mutated on a rollback entity during the rollback schedule is
+// also restored when rolling back. Unregistered components keep their post-rollback values and
+// silently desync peers.
+pub struct RollbackAuditPlugin;
+
+impl Plugin for RollbackAuditPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RegisteredRollbackComponents>();
+        app.init_resource::<RollbackAuditTick>();
+
+        app.add_systems(Startup, collect_registered_rollback_components);
+        app.add_systems(
+            AdvanceWorld,
+            record_rollback_audit_tick.in_set(AdvanceWorldSet::First),
+        );
+        app.add_systems(
+            AdvanceWorld,
+            assert_changed_components_registered.in_set(AdvanceWorldSet::Last),
+        );
+    }
+}
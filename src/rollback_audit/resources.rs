@@ -0,0 +1,17 @@
+use bevy::ecs::component::{ComponentId, Tick};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+// Components that are restored by the load world schedule
+#[derive(Resource, Default)]
+pub struct RegisteredRollbackComponents(pub HashSet<ComponentId>);
+
+// Change tick right before the rollback schedule starts advancing a frame
+#[derive(Resource)]
+pub struct RollbackAuditTick(pub Tick);
+
+impl Default for RollbackAuditTick {
+    fn default() -> Self {
+        Self(Tick::new(0))
+    }
+}
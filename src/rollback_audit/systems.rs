@@ -0,0 +1,86 @@
+use crate::rollback_audit::resources::{RegisteredRollbackComponents, RollbackAuditTick};
+use bevy::ecs::component::ComponentId;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use bevy_ggrs::{LoadWorld, Rollback};
+
+pub fn collect_registered_rollback_components(world: &mut World) {
+    // Every component snapshot plugin adds a system to the load world schedule that writes its
+    // component
+    let mut registered_components: HashSet<ComponentId> =
+        world.schedule_scope(LoadWorld, |world, schedule| {
+            schedule
+                .initialize(world)
+                .expect("failed to initialize load world schedule");
+
+            schedule
+                .systems()
+                .expect("load world schedule is initialized")
+                .flat_map(|(_, system)| system.component_access().writes().collect::<Vec<_>>())
+                .collect()
+        });
+
+    // Mapping the hierarchy of respawned entities also writes parents and children in the load
+    // world schedule, without restoring them. Leave them out, so that hierarchy changes in the
+    // rollback schedule are still flagged.
+    for component_id in [
+        world.init_component::<Parent>(),
+        world.init_component::<Children>(),
+    ] {
+        registered_components.remove(&component_id);
+    }
+
+    world.insert_resource(RegisteredRollbackComponents(registered_components));
+}
+
+pub fn record_rollback_audit_tick(world: &mut World) {
+    let tick = world.change_tick();
+    world.resource_mut::<RollbackAuditTick>().0 = tick;
+}
+
+pub fn assert_changed_components_registered(world: &mut World) {
+    let start_tick = world.resource::<RollbackAuditTick>().0;
+    let this_tick = world.read_change_tick();
+    let registered_components = world.resource::<RegisteredRollbackComponents>();
+    let rollback_component = world
+        .component_id::<Rollback>()
+        .expect("rollback component is registered");
+
+    let mut unregistered_component_names: Vec<String> = Vec::new();
+
+    for entity in world
+        .iter_entities()
+        .filter(|entity| entity.contains_id(rollback_component))
+    {
+        for component_id in entity.archetype().components() {
+            if component_id == rollback_component || registered_components.0.contains(&component_id)
+            {
+                continue;
+            }
+
+            let Some(ticks) = entity.get_change_ticks_by_id(component_id) else {
+                continue;
+            };
+
+            if !ticks.is_changed(start_tick, this_tick) {
+                continue;
+            }
+
+            let name = world
+                .components()
+                .get_info(component_id)
+                .map(|info| info.name().to_string())
+                .unwrap_or_default();
+
+            if !unregistered_component_names.contains(&name) {
+                unregistered_component_names.push(name);
+            }
+        }
+    }
+
+    assert!(
+        unregistered_component_names.is_empty(),
+        "Components mutated in the rollback schedule but not registered for rollback: {}",
+        unregistered_component_names.join(", ")
+    );
+}
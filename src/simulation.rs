@@ -0,0 +1,68 @@
+use crate::artillery::ArtilleryPlugin;
+use crate::connection::FPS;
+use crate::controls::ShipPlugin;
+use crate::damage::DamagePlugin;
+use crate::desync_report::ChecksumReportApp;
+use crate::disconnect::DisconnectPlugin;
+use crate::ocean::WavePlugin;
+use crate::physics::PhysicsPlugin;
+use crate::player::PlayerPlugin;
+use crate::simulation::resources::{checksum_simulation_clock, SimulationClock};
+use crate::simulation::systems::{advance_simulation_clock, map_respawned_hierarchy};
+use crate::wind::WindPlugin;
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+use bevy::transform::systems::{propagate_transforms, sync_simple_transforms};
+use bevy_ggrs::{AdvanceWorld, AdvanceWorldSet, GgrsApp, LoadWorld, LoadWorldSet};
+
+pub mod resources;
+mod systems;
+
+// Everything that runs in the rollback schedule. Does not depend on a window or renderer, so that
+// it can be assembled both into the game and into a headless app.
+pub struct SimulationPlugins;
+
+impl PluginGroup for SimulationPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(SimulationClockPlugin)
+            .add(PhysicsPlugin)
+            .add(WindPlugin)
+            .add(WavePlugin)
+            .add(ShipPlugin)
+            .add(PlayerPlugin)
+            .add(ArtilleryPlugin)
+            .add(DamagePlugin)
+            .add(DisconnectPlugin)
+    }
+}
+
+pub struct SimulationClockPlugin;
+
+impl Plugin for SimulationClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SimulationClock::new(FPS));
+
+        app.rollback_resource_with_copy::<SimulationClock>();
+        app.checksum_and_report_resource::<SimulationClock>(checksum_simulation_clock);
+
+        // Advance the clock before the rollback schedule runs, so that every system in it sees
+        // the same frame
+        app.add_systems(
+            AdvanceWorld,
+            advance_simulation_clock.in_set(AdvanceWorldSet::First),
+        );
+
+        // Global transforms are otherwise only propagated once per update, leaving them stale when
+        // several frames are simulated in one update after a rollback
+        app.add_systems(
+            AdvanceWorld,
+            (sync_simple_transforms, propagate_transforms).in_set(AdvanceWorldSet::First),
+        );
+
+        app.add_systems(
+            LoadWorld,
+            map_respawned_hierarchy.after(LoadWorldSet::Mapping),
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use bevy::utils::FixedState;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+// Frame clock owned by the rollback simulation. Unlike `Time` it only depends on the simulated
+// frame, so resimulated frames see exactly the same values on every peer.
+#[derive(Resource, Reflect, Clone, Copy, Default, Debug)]
+#[reflect(Resource)]
+pub struct SimulationClock {
+    pub frame: u32,
+    pub delta_seconds: f32,
+}
+
+impl SimulationClock {
+    pub fn new(fps: usize) -> Self {
+        SimulationClock {
+            frame: 0,
+            delta_seconds: 1. / fps as f32,
+        }
+    }
+
+    pub fn elapsed_seconds(&self) -> f32 {
+        self.frame as f32 * self.delta_seconds
+    }
+}
+
+pub fn checksum_simulation_clock(value: &SimulationClock) -> u64 {
+    let mut hasher = FixedState.build_hasher();
+    value.frame.hash(&mut hasher);
+    value.delta_seconds.to_bits().hash(&mut hasher);
+
+    #[allow(clippy::manual_hash_one)]
+    hasher.finish()
+}
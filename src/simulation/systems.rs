@@ -0,0 +1,35 @@
+use crate::simulation::resources::SimulationClock;
+use bevy::ecs::entity::MapEntities;
+use bevy::prelude::*;
+use bevy_ggrs::{RollbackEntityMap, RollbackFrameCount};
+
+pub fn advance_simulation_clock(
+    mut clock: ResMut<SimulationClock>,
+    frame_count: Res<RollbackFrameCount>,
+) {
+    clock.frame = frame_count.0 as u32;
+}
+
+// Entities respawned by a rollback, such as a despawned ship and its parts, come back with their
+// hierarchy still pointing at the entities they were saved with. Bevy GGRS only maps the
+// components of entities that survived the rollback.
+pub fn map_respawned_hierarchy(
+    rollback_entity_map: Res<RollbackEntityMap>,
+    mut parent_query: Query<&mut Parent>,
+    mut children_query: Query<&mut Children>,
+) {
+    let mut entity_mapper = rollback_entity_map.as_ref();
+    for (old_entity, new_entity) in rollback_entity_map.iter() {
+        if old_entity == new_entity {
+            continue;
+        }
+
+        if let Ok(mut parent) = parent_query.get_mut(new_entity) {
+            parent.map_entities(&mut entity_mapper);
+        }
+
+        if let Ok(mut children) = children_query.get_mut(new_entity) {
+            children.map_entities(&mut entity_mapper);
+        }
+    }
+}
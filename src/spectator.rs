@@ -0,0 +1,38 @@
+mod components;
+pub mod resources;
+mod systems;
+
+use crate::args::run_conditions::spectate_mode;
+use crate::game_state::states::GameState;
+use crate::spectator::resources::SpectatedPlayer;
+use crate::spectator::systems::{
+    cycle_spectated_player, despawn_frames_behind_indicator, follow_spectated_player,
+    spawn_frames_behind_indicator, update_frames_behind_indicator,
+};
+use bevy::prelude::*;
+
+// Lets non-playing peers follow any ship of an online session
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SpectatedPlayer(0));
+
+        app.add_systems(
+            OnEnter(GameState::InGame),
+            spawn_frames_behind_indicator.run_if(spectate_mode),
+        );
+        app.add_systems(OnExit(GameState::InGame), despawn_frames_behind_indicator);
+
+        app.add_systems(
+            Update,
+            (
+                cycle_spectated_player,
+                follow_spectated_player,
+                update_frames_behind_indicator,
+            )
+                .chain()
+                .run_if(in_state(GameState::InGame).and_then(spectate_mode)),
+        );
+    }
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// Handle of the player whose ship the camera follows when spectating
+#[derive(Resource)]
+pub struct SpectatedPlayer(pub usize);
@@ -0,0 +1,77 @@
+use crate::args::resources::Args;
+use crate::camera::resources::MainCamera;
+use crate::connection::systems::RollbackConfig;
+use crate::focal_point::components::CameraFocalPoint;
+use crate::spectator::components::FramesBehindIndicator;
+use crate::spectator::resources::SpectatedPlayer;
+use bevy::prelude::*;
+use bevy_ggrs::Session;
+
+pub fn cycle_spectated_player(
+    keys: Res<ButtonInput<KeyCode>>,
+    args: Res<Args>,
+    mut spectated_player: ResMut<SpectatedPlayer>,
+) {
+    if keys.just_pressed(KeyCode::Tab) {
+        spectated_player.0 = (spectated_player.0 + 1) % args.num_players;
+    }
+}
+
+pub fn follow_spectated_player(
+    mut camera_focal_point_query: Query<&mut CameraFocalPoint>,
+    spectated_player: Res<SpectatedPlayer>,
+) {
+    for mut camera_focal_point in &mut camera_focal_point_query {
+        camera_focal_point.handle = spectated_player.0;
+    }
+}
+
+pub fn spawn_frames_behind_indicator(mut commands: Commands, main_camera: Res<MainCamera>) {
+    commands.spawn((
+        // Seems to be required in dev builds since using editor plugin results in multiple
+        // cameras
+        TargetCamera(main_camera.id),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 18.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(16.),
+            right: Val::Px(16.),
+            ..default()
+        }),
+        FramesBehindIndicator,
+    ));
+}
+
+pub fn update_frames_behind_indicator(
+    session: Option<Res<Session<RollbackConfig>>>,
+    spectated_player: Res<SpectatedPlayer>,
+    mut indicator_query: Query<&mut Text, With<FramesBehindIndicator>>,
+) {
+    let Some(Session::Spectator(spectator_session)) = session.as_deref() else {
+        return;
+    };
+
+    for mut text in &mut indicator_query {
+        text.sections[0].value = format!(
+            "Spectating player {} | {} frames behind",
+            spectated_player.0 + 1,
+            spectator_session.frames_behind_host()
+        );
+    }
+}
+
+pub fn despawn_frames_behind_indicator(
+    mut commands: Commands,
+    indicator_query: Query<Entity, With<FramesBehindIndicator>>,
+) {
+    for entity in &indicator_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,9 +1,11 @@
 use crate::args::run_conditions::p2p_mode;
 use crate::game_state::states::GameState;
+use crate::stats::events::DesyncDetected;
 use crate::stats::resources::NetworkStatsTimer;
-use crate::stats::systems::{print_events, print_network_stats};
+use crate::stats::systems::{print_events, print_network_stats, print_projectile_hits};
 use bevy::prelude::*;
 
+pub mod events;
 mod resources;
 mod systems;
 
@@ -11,11 +13,17 @@ pub struct StatsPlugin;
 
 impl Plugin for StatsPlugin {
     fn build(&self, app: &mut App) {
+        app.add_event::<DesyncDetected>();
+
         app.insert_resource(NetworkStatsTimer(Timer::from_seconds(
             2.0,
             TimerMode::Repeating,
         )));
         app.add_systems(Update, print_events.run_if(in_state(GameState::InGame)));
+        app.add_systems(
+            Update,
+            print_projectile_hits.run_if(in_state(GameState::InGame)),
+        );
 
         app.add_systems(
             Update,
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+#[derive(Event)]
+pub struct DesyncDetected {
+    pub frame: i32,
+    pub local_checksum: u128,
+    pub remote_checksum: u128,
+}
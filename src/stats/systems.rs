@@ -1,4 +1,6 @@
+use crate::artillery::events::ProjectileHit;
 use crate::connection::systems::RollbackConfig;
+use crate::stats::events::DesyncDetected;
 use crate::stats::resources::NetworkStatsTimer;
 use bevy::prelude::*;
 use bevy_ggrs::prelude::*;
@@ -19,28 +21,59 @@ pub fn print_network_stats(
                     }
                 }
             }
+            Session::Spectator(s) => {
+                if let Ok(stats) = s.network_stats() {
+                    println!("NetworkStats for host: {:?}", stats);
+                }
+            }
             _ => panic!("This examples focuses on p2p."),
         }
     }
 }
 
-pub(crate) fn print_events(mut session: ResMut<Session<RollbackConfig>>) {
-    if let Session::P2P(s) = session.as_mut() {
-        for event in s.events() {
-            match event {
-                GgrsEvent::Disconnected { .. } | GgrsEvent::NetworkInterrupted { .. } => {
-                    warn!("GGRS event: {event:?}")
-                }
-                GgrsEvent::DesyncDetected {
+pub(crate) fn print_events(
+    mut session: ResMut<Session<RollbackConfig>>,
+    mut desync_event_writer: EventWriter<DesyncDetected>,
+) {
+    let events: Vec<GgrsEvent<RollbackConfig>> = match session.as_mut() {
+        Session::P2P(s) => s.events().collect(),
+        Session::Spectator(s) => s.events().collect(),
+        Session::SyncTest(_) => Vec::new(),
+    };
+
+    for event in events {
+        match event {
+            GgrsEvent::Disconnected { .. } | GgrsEvent::NetworkInterrupted { .. } => {
+                warn!("GGRS event: {event:?}")
+            }
+            GgrsEvent::DesyncDetected {
+                local_checksum,
+                remote_checksum,
+                frame,
+                ..
+            } => {
+                error!("Desync on frame {frame}. Local checksum: {local_checksum:X}, remote checksum: {remote_checksum:X}");
+                desync_event_writer.send(DesyncDetected {
+                    frame,
                     local_checksum,
                     remote_checksum,
-                    frame,
-                    ..
-                } => {
-                    error!("Desync on frame {frame}. Local checksum: {local_checksum:X}, remote checksum: {remote_checksum:X}");
-                }
-                _ => info!("GGRS event: {event:?}"),
+                });
             }
+            _ => info!("GGRS event: {event:?}"),
         }
     }
 }
+
+// Hits are sent again when frames are simulated again after a rollback, so the same hit may be
+// printed more than once
+pub fn print_projectile_hits(mut projectile_hit_event_reader: EventReader<ProjectileHit>) {
+    for hit in projectile_hit_event_reader.read() {
+        info!(
+            "Player {} hit player {} at {} with speed {}",
+            hit.shooter,
+            hit.target,
+            hit.point,
+            hit.velocity.length()
+        );
+    }
+}
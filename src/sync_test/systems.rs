@@ -17,7 +17,12 @@ pub fn start_sync_test_session(
         .expect("prediction window can't be 0")
         .with_fps(FPS)
         .expect("FPS can't be 0")
-        .with_input_delay(INPUT_DELAY)
+        // Replays hold the inputs as they were simulated, already delayed when recorded
+        .with_input_delay(if args.replay.is_some() {
+            0
+        } else {
+            INPUT_DELAY
+        })
         // GGRS will simulate a rollback every frame and re-simulate the last n states, where n is the given
         // check_distance. All expensive operations are skipped if the check distance is 0, enabling use of synctest
         // mode for general local play.
@@ -1,6 +1,10 @@
 pub mod aerodynamics;
+pub mod ballistics;
+pub mod collision_detection;
 pub mod f32_extensions;
 pub mod hash;
+pub mod hull_voxelization;
 pub mod linear_algebra;
+pub mod noise;
 pub mod vec2_extensions;
 pub mod water_mechanics;
@@ -0,0 +1,314 @@
+use bevy::prelude::*;
+
+// Corrections of the aim when leading a moving target. Fixed rather than iterated until converged,
+// so that every peer arrives at the same aim.
+const LEAD_ITERATIONS: usize = 6;
+
+// Lower of the two elevations that carry a projectile fired at `speed` over `horizontal_distance`
+// while climbing `height`, or none if the target is out of reach.
+// https://en.wikipedia.org/wiki/Projectile_motion#Angle_%CE%B8_required_to_hit_coordinate_(x,_y)
+pub fn elevation_to_hit(
+    horizontal_distance: f32,
+    height: f32,
+    speed: f32,
+    gravity: f32,
+) -> Option<f32> {
+    if horizontal_distance <= f32::EPSILON || speed <= f32::EPSILON {
+        return None;
+    }
+
+    if gravity <= f32::EPSILON {
+        return Some(height.atan2(horizontal_distance));
+    }
+
+    let speed_squared = speed.powi(2);
+    let discriminant = speed_squared.powi(2)
+        - gravity * (gravity * horizontal_distance.powi(2) + 2. * height * speed_squared);
+    if discriminant < 0. {
+        return None;
+    }
+
+    Some(((speed_squared - discriminant.sqrt()) / (gravity * horizontal_distance)).atan())
+}
+
+// How a projectile flies through the air, stepped frame by frame the same way the rollback physics
+// step it
+#[derive(Clone, Copy, Debug)]
+pub struct Flight {
+    pub gravity: Vec3,
+    // Air the projectile drags against, and drifts along with
+    pub wind: Vec3,
+    // Drag per squared speed through the air, per kilogram of projectile
+    pub drag_per_mass: f32,
+    // Share of its velocity the projectile keeps per second
+    pub damping: f32,
+    pub delta_seconds: f32,
+    // Frames after which a projectile still in the air is no longer followed
+    pub max_frames: usize,
+}
+
+// Where and when a projectile comes down through a height
+pub struct Descent {
+    pub time: f32,
+    pub impact: Vec3,
+}
+
+impl Flight {
+    pub fn step(&self, position: Vec3, velocity: Vec3) -> (Vec3, Vec3) {
+        let air_velocity = velocity - self.wind;
+        let drag = -air_velocity * air_velocity.length() * self.drag_per_mass;
+
+        let velocity = (velocity + (drag + self.gravity) * self.delta_seconds)
+            * self.damping.powf(self.delta_seconds);
+
+        (position + velocity * self.delta_seconds, velocity)
+    }
+
+    // Positions of a projectile launched from `origin` at `velocity`, one per frame followed
+    pub fn path(&self, origin: Vec3, velocity: Vec3) -> impl Iterator<Item = Vec3> + '_ {
+        std::iter::successors(Some((origin, velocity)), |&(position, velocity)| {
+            Some(self.step(position, velocity))
+        })
+        .map(|(position, _)| position)
+        .take(self.max_frames + 1)
+    }
+
+    // Point at which a projectile launched from `origin` at `velocity` comes down through `height`,
+    // or none if it does not within the frames followed
+    pub fn descend_to(&self, origin: Vec3, velocity: Vec3, height: f32) -> Option<Descent> {
+        let mut path = self.path(origin, velocity);
+        let mut previous = path.next()?;
+
+        for (frame, position) in path.enumerate() {
+            if previous.y >= height && position.y < height {
+                let fraction = (previous.y - height) / (previous.y - position.y);
+                return Some(Descent {
+                    time: (frame as f32 + fraction) * self.delta_seconds,
+                    impact: previous.lerp(position, fraction),
+                });
+            }
+
+            previous = position;
+        }
+
+        None
+    }
+}
+
+pub struct Lead {
+    // Angle above the horizon to fire at
+    pub elevation: f32,
+    // Horizontal offset across the firing axis from where the projectile comes down to where the
+    // target will be, which cannons that can't traverse can't take out
+    pub miss: Vec3,
+}
+
+// Elevation of a cannon firing along the horizontal `firing_axis` that brings a projectile down
+// level with a target moving at `target_velocity`, as far along the axis as the target will be.
+// The projectile carries the velocity of the shooter. Starts from the elevation that would carry a
+// projectile in a vacuum as far as the target, then corrects it by how far the flight with drag
+// and wind falls short of or overshoots the target.
+pub fn lead_target(
+    origin: Vec3,
+    shooter_velocity: Vec3,
+    firing_axis: Vec3,
+    target: Vec3,
+    target_velocity: Vec3,
+    speed: f32,
+    flight: &Flight,
+) -> Option<Lead> {
+    let height = target.y - origin.y;
+    let mut aim_distance = (target - origin).dot(firing_axis);
+    let mut lead = None;
+
+    for _ in 0..LEAD_ITERATIONS {
+        let elevation = elevation_to_hit(aim_distance, height, speed, -flight.gravity.y)?;
+        let velocity =
+            (firing_axis * elevation.cos() + Vec3::Y * elevation.sin()) * speed + shooter_velocity;
+        let descent = flight.descend_to(origin, velocity, target.y)?;
+
+        let offset =
+            (target + target_velocity * descent.time - descent.impact) * Vec3::new(1., 0., 1.);
+        let shortfall = offset.dot(firing_axis);
+        aim_distance += shortfall;
+
+        lead = Some(Lead {
+            elevation,
+            miss: offset - firing_axis * shortfall,
+        });
+    }
+
+    lead
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAVITY: f32 = 9.81;
+    const SPEED: f32 = 24.;
+
+    #[test]
+    fn elevation_carries_the_projectile_over_the_distance() {
+        let elevation = elevation_to_hit(40., 0., SPEED, GRAVITY).unwrap();
+
+        // Range on flat ground is v^2 sin(2θ) / g
+        let range = SPEED.powi(2) * (2. * elevation).sin() / GRAVITY;
+        assert!((range - 40.).abs() < 0.01);
+        // The lower of the two elevations reaching that far
+        assert!(elevation < std::f32::consts::FRAC_PI_4);
+    }
+
+    #[test]
+    fn targets_beyond_the_maximum_range_are_out_of_reach() {
+        let maximum_range = SPEED.powi(2) / GRAVITY;
+
+        assert!(elevation_to_hit(maximum_range * 0.99, 0., SPEED, GRAVITY).is_some());
+        assert!(elevation_to_hit(maximum_range * 1.01, 0., SPEED, GRAVITY).is_none());
+        assert!(elevation_to_hit(10., 30., SPEED, GRAVITY).is_none());
+    }
+
+    fn flight(drag_per_mass: f32, wind: Vec3) -> Flight {
+        Flight {
+            gravity: Vec3::NEG_Y * GRAVITY,
+            wind,
+            drag_per_mass,
+            damping: 1.,
+            delta_seconds: 1. / 60.,
+            max_frames: 600,
+        }
+    }
+
+    // Where a projectile fired along the axis at the elevation of the lead comes down, and where the
+    // target is by then
+    fn impact(lead: &Lead, target: Vec3, target_velocity: Vec3, flight: &Flight) -> (Vec3, Vec3) {
+        let velocity = Vec3::new(lead.elevation.cos(), lead.elevation.sin(), 0.) * SPEED;
+        let descent = flight.descend_to(Vec3::ZERO, velocity, target.y).unwrap();
+        (descent.impact, target + target_velocity * descent.time)
+    }
+
+    #[test]
+    fn lead_hits_a_target_sailing_away() {
+        let flight = flight(0., Vec3::ZERO);
+        let target = Vec3::new(30., 0., 0.);
+        let target_velocity = Vec3::new(3., 0., 0.);
+
+        let lead = lead_target(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::X,
+            target,
+            target_velocity,
+            SPEED,
+            &flight,
+        )
+        .unwrap();
+
+        let (impact, target_at_impact) = impact(&lead, target, target_velocity, &flight);
+        let miss = impact.distance(target_at_impact);
+        assert!(miss < 0.1, "missed by {miss} meters");
+
+        // Leading aims higher than at the target's current position
+        let elevation_at_target = elevation_to_hit(target.x, 0., SPEED, GRAVITY).unwrap();
+        assert!(lead.elevation > elevation_at_target);
+    }
+
+    #[test]
+    fn lead_makes_up_for_drag_and_wind() {
+        let flight = flight(0.002, Vec3::new(0., 0., 6.));
+        let target = Vec3::new(30., 0., 0.);
+
+        let lead = lead_target(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::X,
+            target,
+            Vec3::ZERO,
+            SPEED,
+            &flight,
+        )
+        .unwrap();
+
+        let (impact, _) = impact(&lead, target, Vec3::ZERO, &flight);
+        assert!((impact.x - target.x).abs() < 0.1, "came down at {impact}");
+        // Drag takes more elevation than a vacuum would
+        assert!(lead.elevation > elevation_to_hit(target.x, 0., SPEED, GRAVITY).unwrap());
+        // The wind blows the ball downwind, which is left as the miss across the axis
+        assert!((lead.miss.z + impact.z).abs() < 0.01);
+        assert!(lead.miss.z < 0.);
+    }
+
+    #[test]
+    fn target_ahead_of_the_beam_is_ranged_along_the_firing_axis() {
+        let flight = flight(0., Vec3::ZERO);
+        let target = Vec3::new(20., 0., 15.);
+
+        let lead = lead_target(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::X,
+            target,
+            Vec3::ZERO,
+            SPEED,
+            &flight,
+        )
+        .unwrap();
+
+        // The ball comes down abeam of the target rather than as far out as the target is
+        let (impact, _) = impact(&lead, target, Vec3::ZERO, &flight);
+        assert!((impact.x - target.x).abs() < 0.1, "came down at {impact}");
+        assert!(lead.miss.abs_diff_eq(Vec3::new(0., 0., 15.), 0.1));
+    }
+
+    // Holds in still air, where the ball only drags against its velocity relative to the ships
+    #[test]
+    fn only_the_velocity_relative_to_the_shooter_is_led() {
+        let flight = flight(0., Vec3::ZERO);
+        let velocity = Vec3::new(0., 0., 2.);
+        let target = Vec3::new(20., 0., 0.);
+
+        let elevation = |shooter_velocity: Vec3, target_velocity: Vec3| {
+            lead_target(
+                Vec3::ZERO,
+                shooter_velocity,
+                Vec3::X,
+                target,
+                target_velocity,
+                SPEED,
+                &flight,
+            )
+            .unwrap()
+            .elevation
+        };
+        assert!((elevation(velocity, velocity) - elevation(Vec3::ZERO, Vec3::ZERO)).abs() < 0.001);
+    }
+
+    #[test]
+    fn descent_is_taken_on_the_way_down() {
+        let flight = flight(0., Vec3::ZERO);
+        let velocity = Vec3::new(0., 10., 0.);
+
+        let descent = flight.descend_to(Vec3::ZERO, velocity, 0.).unwrap();
+        // Stepping lands within a frame of the exact parabola
+        assert!((descent.time - 2. * 10. / GRAVITY).abs() < 1. / 30.);
+        assert!(descent.impact.y.abs() < 0.001);
+
+        // The apex is at v^2 / 2g, about 5.1 meters
+        assert!(flight.descend_to(Vec3::ZERO, velocity, 6.).is_none());
+        // Already below the height and falling
+        assert!(flight.descend_to(Vec3::ZERO, -velocity, 1.).is_none());
+    }
+
+    #[test]
+    fn drag_brings_the_ball_down_short_of_the_vacuum_range() {
+        let velocity = Vec3::new(SPEED, SPEED, 0.) / 2_f32.sqrt();
+        let in_vacuum = flight(0., Vec3::ZERO)
+            .descend_to(Vec3::ZERO, velocity, 0.)
+            .unwrap();
+        let with_drag = flight(0.002, Vec3::ZERO)
+            .descend_to(Vec3::ZERO, velocity, 0.)
+            .unwrap();
+
+        assert!(with_drag.impact.x < in_vacuum.impact.x - 1.);
+    }
+}
@@ -0,0 +1,326 @@
+use bevy::math::{Mat3, Vec3};
+
+// Collider shape placed in world space
+#[derive(Clone, Copy, Debug)]
+pub enum WorldShape {
+    Sphere {
+        center: Vec3,
+        radius: f32,
+    },
+    // Oriented box, the columns of the rotation being its local axes
+    Cuboid {
+        center: Vec3,
+        rotation: Mat3,
+        half_extents: Vec3,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Contact {
+    pub point: Vec3,
+    // Points from the first shape towards the second
+    pub normal: Vec3,
+    pub penetration_depth: f32,
+}
+
+impl Contact {
+    fn flipped(self) -> Self {
+        Self {
+            normal: -self.normal,
+            ..self
+        }
+    }
+}
+
+impl WorldShape {
+    pub fn center(&self) -> Vec3 {
+        match self {
+            WorldShape::Sphere { center, .. } | WorldShape::Cuboid { center, .. } => *center,
+        }
+    }
+
+    // Radius of the sphere enclosing the shape, used to cheaply rule out pairs that can't touch
+    pub fn bounding_radius(&self) -> f32 {
+        match self {
+            WorldShape::Sphere { radius, .. } => *radius,
+            WorldShape::Cuboid { half_extents, .. } => half_extents.length(),
+        }
+    }
+}
+
+pub fn contact(a: &WorldShape, b: &WorldShape) -> Option<Contact> {
+    match (*a, *b) {
+        (
+            WorldShape::Sphere {
+                center: center_a,
+                radius: radius_a,
+            },
+            WorldShape::Sphere {
+                center: center_b,
+                radius: radius_b,
+            },
+        ) => sphere_sphere_contact(center_a, radius_a, center_b, radius_b),
+        (
+            WorldShape::Sphere { center, radius },
+            WorldShape::Cuboid {
+                center: box_center,
+                rotation,
+                half_extents,
+            },
+        ) => cuboid_sphere_contact(box_center, rotation, half_extents, center, radius)
+            .map(Contact::flipped),
+        (
+            WorldShape::Cuboid {
+                center: box_center,
+                rotation,
+                half_extents,
+            },
+            WorldShape::Sphere { center, radius },
+        ) => cuboid_sphere_contact(box_center, rotation, half_extents, center, radius),
+        (
+            WorldShape::Cuboid {
+                center: center_a,
+                rotation: rotation_a,
+                half_extents: half_extents_a,
+            },
+            WorldShape::Cuboid {
+                center: center_b,
+                rotation: rotation_b,
+                half_extents: half_extents_b,
+            },
+        ) => cuboid_cuboid_contact(
+            (center_a, rotation_a, half_extents_a),
+            (center_b, rotation_b, half_extents_b),
+        ),
+    }
+}
+
+fn sphere_sphere_contact(
+    center_a: Vec3,
+    radius_a: f32,
+    center_b: Vec3,
+    radius_b: f32,
+) -> Option<Contact> {
+    let offset = center_b - center_a;
+    let distance = offset.length();
+    let penetration_depth = radius_a + radius_b - distance;
+    if penetration_depth <= 0. {
+        return None;
+    }
+
+    // Concentric spheres can be pushed apart in any direction, as long as it is always the same
+    let normal = offset.try_normalize().unwrap_or(Vec3::Y);
+
+    Some(Contact {
+        point: center_a + normal * (radius_a - penetration_depth / 2.),
+        normal,
+        penetration_depth,
+    })
+}
+
+fn cuboid_sphere_contact(
+    box_center: Vec3,
+    rotation: Mat3,
+    half_extents: Vec3,
+    sphere_center: Vec3,
+    radius: f32,
+) -> Option<Contact> {
+    let local_center = rotation.transpose() * (sphere_center - box_center);
+    let closest_point = closest_point(box_center, rotation, half_extents, sphere_center);
+
+    if local_center.abs().cmpgt(half_extents).any() {
+        // Sphere center is outside of the box
+        let offset = sphere_center - closest_point;
+        let distance = offset.length();
+        if distance >= radius {
+            return None;
+        }
+
+        let normal = offset / distance;
+        return Some(Contact {
+            point: closest_point,
+            normal,
+            penetration_depth: radius - distance,
+        });
+    }
+
+    // Sphere center is inside of the box, so push it out through the closest face
+    let face_distances = half_extents - local_center.abs();
+    let axis = if face_distances.x <= face_distances.y && face_distances.x <= face_distances.z {
+        0
+    } else if face_distances.y <= face_distances.z {
+        1
+    } else {
+        2
+    };
+
+    let sign = if local_center[axis] < 0. { -1. } else { 1. };
+    let normal = rotation.col(axis) * sign;
+
+    Some(Contact {
+        point: sphere_center,
+        normal,
+        penetration_depth: face_distances[axis] + radius,
+    })
+}
+
+// Half of the length of given box projected onto an axis
+fn projected_radius(rotation: Mat3, half_extents: Vec3, axis: Vec3) -> f32 {
+    (0..3)
+        .map(|index| half_extents[index] * rotation.col(index).dot(axis).abs())
+        .sum()
+}
+
+// Point in given box closest to a point
+fn closest_point(center: Vec3, rotation: Mat3, half_extents: Vec3, point: Vec3) -> Vec3 {
+    let local_point = rotation.transpose() * (point - center);
+    center + rotation * local_point.clamp(-half_extents, half_extents)
+}
+
+// Separating axis test over the face normals of both boxes and the cross products of their edges,
+// pushing the boxes apart along the axis they overlap the least on
+fn cuboid_cuboid_contact(
+    (center_a, rotation_a, half_extents_a): (Vec3, Mat3, Vec3),
+    (center_b, rotation_b, half_extents_b): (Vec3, Mat3, Vec3),
+) -> Option<Contact> {
+    let offset = center_b - center_a;
+
+    let mut axes = Vec::with_capacity(15);
+    for index in 0..3 {
+        axes.push(rotation_a.col(index));
+        axes.push(rotation_b.col(index));
+    }
+    for index_a in 0..3 {
+        for index_b in 0..3 {
+            // Parallel edges span no plane, and a face normal will already separate them
+            if let Some(axis) = rotation_a
+                .col(index_a)
+                .cross(rotation_b.col(index_b))
+                .try_normalize()
+            {
+                axes.push(axis);
+            }
+        }
+    }
+
+    let mut best: Option<(f32, Vec3)> = None;
+    for axis in axes {
+        let distance = offset.dot(axis);
+        let overlap = projected_radius(rotation_a, half_extents_a, axis)
+            + projected_radius(rotation_b, half_extents_b, axis)
+            - distance.abs();
+
+        if overlap <= 0. {
+            return None;
+        }
+
+        if best.map_or(true, |(best_overlap, _)| overlap < best_overlap) {
+            let normal = if distance < 0. { -axis } else { axis };
+            best = Some((overlap, normal));
+        }
+    }
+
+    let (penetration_depth, normal) = best?;
+
+    // Halfway between the points of both boxes closest to the center of the other one, which
+    // moves smoothly along with the boxes unlike their corners
+    let point = (closest_point(center_a, rotation_a, half_extents_a, center_b)
+        + closest_point(center_b, rotation_b, half_extents_b, center_a))
+        / 2.;
+
+    Some(Contact {
+        point,
+        normal,
+        penetration_depth,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn unit_box(center: Vec3, rotation: Mat3) -> WorldShape {
+        WorldShape::Cuboid {
+            center,
+            rotation,
+            half_extents: Vec3::ONE,
+        }
+    }
+
+    fn sphere(center: Vec3, radius: f32) -> WorldShape {
+        WorldShape::Sphere { center, radius }
+    }
+
+    #[test]
+    fn sphere_deep_inside_box_is_pushed_out_through_the_closest_face() {
+        let contact = contact(
+            &unit_box(Vec3::ZERO, Mat3::IDENTITY),
+            &sphere(Vec3::new(0., 0.75, 0.), 0.5),
+        )
+        .unwrap();
+
+        assert!(contact.normal.abs_diff_eq(Vec3::Y, EPSILON));
+        assert!((contact.penetration_depth - 0.75).abs() < EPSILON);
+    }
+
+    #[test]
+    fn sphere_touching_an_edge_of_a_rotated_box_is_pushed_away_from_the_edge() {
+        // Turned by 45 degrees about Y, so that an edge of the box points along X
+        let rotation = Mat3::from_rotation_y(std::f32::consts::FRAC_PI_4);
+        let edge = Vec3::new(std::f32::consts::SQRT_2, 1., 0.);
+        let direction = Vec3::new(1., 1., 0.).normalize();
+        let box_shape = unit_box(Vec3::ZERO, rotation);
+
+        let contact = contact(&box_shape, &sphere(edge + direction * 0.4, 0.5)).unwrap();
+        assert!(contact.normal.abs_diff_eq(direction, EPSILON));
+        assert!((contact.penetration_depth - 0.1).abs() < EPSILON);
+        assert!(contact.point.abs_diff_eq(edge, EPSILON));
+
+        // Argument order flips the normal
+        let flipped = super::contact(&sphere(edge + direction * 0.4, 0.5), &box_shape).unwrap();
+        assert!(flipped.normal.abs_diff_eq(-direction, EPSILON));
+    }
+
+    #[test]
+    fn sphere_beside_a_box_has_no_contact() {
+        let box_shape = unit_box(Vec3::ZERO, Mat3::IDENTITY);
+
+        assert!(contact(&box_shape, &sphere(Vec3::new(1.6, 0., 0.), 0.5)).is_none());
+        // Within the bounding radius of the corner, but not the box itself
+        assert!(contact(&box_shape, &sphere(Vec3::new(1.4, 1.4, 0.), 0.5)).is_none());
+    }
+
+    #[test]
+    fn overlapping_boxes_separate_along_the_axis_of_least_overlap() {
+        let contact = contact(
+            &unit_box(Vec3::ZERO, Mat3::IDENTITY),
+            &unit_box(Vec3::new(1.8, 0.5, 0.), Mat3::IDENTITY),
+        )
+        .unwrap();
+
+        assert!(contact.normal.abs_diff_eq(Vec3::X, EPSILON));
+        assert!((contact.penetration_depth - 0.2).abs() < EPSILON);
+    }
+
+    #[test]
+    fn boxes_separated_only_along_an_edge_axis_have_no_contact() {
+        // Edges crossing at right angles: the face axes of both boxes overlap, but the cross
+        // product of the edges separates them
+        let rotation_a = Mat3::from_rotation_z(std::f32::consts::FRAC_PI_4);
+        let rotation_b = Mat3::from_rotation_x(std::f32::consts::FRAC_PI_4);
+        let separation = 2. * std::f32::consts::SQRT_2 + 0.05;
+
+        assert!(contact(
+            &unit_box(Vec3::ZERO, rotation_a),
+            &unit_box(Vec3::new(0., separation, 0.), rotation_b),
+        )
+        .is_none());
+        assert!(contact(
+            &unit_box(Vec3::ZERO, rotation_a),
+            &unit_box(Vec3::new(0., separation - 0.1, 0.), rotation_b),
+        )
+        .is_some());
+    }
+}
@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+
+// Vertical run of voxels sharing the same horizontal position
+#[derive(Clone, Copy, Default, Debug)]
+pub struct VoxelColumn {
+    pub x: f32,
+    pub z: f32,
+    // Center of the lowest voxel
+    pub bottom: f32,
+    pub voxel_count: u32,
+}
+
+fn greatest_common_divisor(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        greatest_common_divisor(b, a % b)
+    }
+}
+
+// Normals of the planes bounding the simplified hull, one for every distinct direction with integer
+// components from -2 to 2. Integer directions keep the planes exactly the same on every platform.
+pub fn bounding_plane_normals() -> Vec<Vec3> {
+    let mut normals = Vec::new();
+    for x in -2..=2_i32 {
+        for y in -2..=2_i32 {
+            for z in -2..=2_i32 {
+                let divisor =
+                    greatest_common_divisor(greatest_common_divisor(x.abs(), y.abs()), z.abs());
+                if divisor == 1 {
+                    normals.push(Vec3::new(x as f32, y as f32, z as f32));
+                }
+            }
+        }
+    }
+    normals
+}
+
+// Fills the convex hull of given vertices with cubic voxels. The hull is approximated by pushing a
+// plane along each of the bounding plane normals until it touches the outermost vertex, which is
+// close enough for buoyancy and only ever needs dot products.
+pub fn voxelize_convex_hull(vertices: &[Vec3], voxel_size: f32) -> Vec<VoxelColumn> {
+    let planes: Vec<(Vec3, f32)> = bounding_plane_normals()
+        .into_iter()
+        .map(|normal| {
+            let distance = vertices
+                .iter()
+                .map(|vertex| normal.dot(*vertex))
+                .fold(f32::NEG_INFINITY, f32::max);
+            (normal, distance)
+        })
+        .collect();
+
+    let min = vertices.iter().copied().fold(Vec3::INFINITY, Vec3::min);
+    let max = vertices.iter().copied().fold(Vec3::NEG_INFINITY, Vec3::max);
+    let voxel_counts = ((max - min) / voxel_size).ceil().as_uvec3();
+
+    let voxel_center =
+        |x: u32, y: u32, z: u32| min + (Vec3::new(x as f32, y as f32, z as f32) + 0.5) * voxel_size;
+    let is_inside = |point: Vec3| {
+        planes
+            .iter()
+            .all(|(normal, distance)| normal.dot(point) <= *distance)
+    };
+
+    let mut columns = Vec::new();
+    for x in 0..voxel_counts.x {
+        for z in 0..voxel_counts.z {
+            // The hull is convex, so the voxels inside it are contiguous along every column
+            let inside: Vec<u32> = (0..voxel_counts.y)
+                .filter(|y| is_inside(voxel_center(x, *y, z)))
+                .collect();
+
+            if let (Some(first), Some(last)) = (inside.first(), inside.last()) {
+                let bottom = voxel_center(x, *first, z);
+                columns.push(VoxelColumn {
+                    x: bottom.x,
+                    z: bottom.z,
+                    bottom: bottom.y,
+                    voxel_count: last - first + 1,
+                });
+            }
+        }
+    }
+
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube_vertices(size: f32) -> Vec<Vec3> {
+        let mut vertices = Vec::new();
+        for x in [0., size] {
+            for y in [0., size] {
+                for z in [0., size] {
+                    vertices.push(Vec3::new(x, y, z));
+                }
+            }
+        }
+        vertices
+    }
+
+    fn volume(columns: &[VoxelColumn], voxel_size: f32) -> f32 {
+        let voxel_count: u32 = columns.iter().map(|column| column.voxel_count).sum();
+        voxel_count as f32 * voxel_size.powi(3)
+    }
+
+    #[test]
+    fn cube_is_filled_completely() {
+        let columns = voxelize_convex_hull(&cube_vertices(2.), 0.5);
+
+        assert_eq!(columns.len(), 16);
+        for column in &columns {
+            assert_eq!(column.voxel_count, 4);
+            assert_eq!(column.bottom, 0.25);
+        }
+        assert_eq!(volume(&columns, 0.5), 8.);
+    }
+
+    #[test]
+    fn octahedron_keeps_its_shape_and_volume() {
+        let vertices = [
+            Vec3::X,
+            Vec3::NEG_X,
+            Vec3::Y,
+            Vec3::NEG_Y,
+            Vec3::Z,
+            Vec3::NEG_Z,
+        ]
+        .map(|vertex| vertex * 2.);
+        let columns = voxelize_convex_hull(&vertices, 0.1);
+
+        // Columns get shorter away from the center
+        let center_column = columns
+            .iter()
+            .find(|column| column.x.abs() < 0.1 && column.z.abs() < 0.1)
+            .unwrap();
+        assert!(columns
+            .iter()
+            .all(|column| column.voxel_count <= center_column.voxel_count));
+
+        // An octahedron of radius 2 holds 4/3 * 2^3 cubic meters
+        let expected_volume = 4. / 3. * 8.;
+        assert!((volume(&columns, 0.1) - expected_volume).abs() < expected_volume * 0.05);
+    }
+}
@@ -5,10 +5,6 @@ pub fn face_normal(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> [f32; 3] {
     (b - a).cross(c - a).normalize().into()
 }
 
-pub fn is_facing(source_direction: Vec3, source_position: Vec3, target_position: Vec3) -> bool {
-    (target_position - source_position).dot(source_direction) > 0.
-}
-
 pub fn angle_between_perpendicular(vector: Vec3, normal: Vec3) -> f32 {
     (vector.dot(normal) / vector.length()).asin()
 }
@@ -0,0 +1,70 @@
+// Smooth noise between -1 and 1 along a line, interpolating seeded random values at whole numbers.
+// Built from integer hashing so that it comes out the same on every platform.
+pub fn value_noise(seed: u32, x: f32) -> f32 {
+    let cell = x.floor();
+    let t = x - cell;
+    let smooth_t = t * t * (3. - 2. * t);
+
+    let start = lattice_value(seed, cell as i32);
+    let end = lattice_value(seed, cell as i32 + 1);
+
+    start + (end - start) * smooth_t
+}
+
+// https://nullprogram.com/blog/2018/07/31/
+fn lattice_value(seed: u32, index: i32) -> f32 {
+    let mut hash = (index as u32) ^ seed.wrapping_mul(0x9e37_79b9);
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x7feb_352d);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x846c_a68b);
+    hash ^= hash >> 16;
+
+    // Keep the 24 bits an f32 represents exactly
+    (hash >> 8) as f32 / (1 << 23) as f32 - 1.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_stays_between_minus_one_and_one() {
+        for seed in 0..8 {
+            for step in -1000..1000 {
+                let noise = value_noise(seed, step as f32 * 0.37);
+                assert!((-1. ..=1.).contains(&noise), "{noise} out of range");
+            }
+        }
+    }
+
+    #[test]
+    fn noise_passes_through_the_lattice_values() {
+        for index in -5..5 {
+            assert_eq!(value_noise(7, index as f32), lattice_value(7, index));
+        }
+    }
+
+    #[test]
+    fn noise_changes_smoothly() {
+        let step = 0.001;
+        for index in -1000..1000 {
+            let x = index as f32 * 0.01;
+            let change = (value_noise(3, x + step) - value_noise(3, x)).abs();
+            // The smoothstep is at most 1.5 times as steep as a line between lattice values two
+            // apart
+            assert!(
+                change <= 3. * step + f32::EPSILON,
+                "jumps by {change} at {x}"
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_give_different_noise() {
+        let differing = (0..100)
+            .filter(|index| lattice_value(1, *index) != lattice_value(2, *index))
+            .count();
+        assert!(differing > 90);
+    }
+}
@@ -3,16 +3,45 @@ use std::f32::consts::PI;
 
 const GRAVITY: f32 = 10.;
 
+// Wavelength of the dominant wave grows with the square of the wind speed, as in a fully developed
+// sea, tuned so that a moderate breeze of 6 m/s raises 64 m long waves
+const WAVE_LENGTH_PER_SQUARED_WIND_SPEED: f32 = 64. / 36.;
+// The wind only blows over a limited stretch of open water, the fetch, which caps how long waves
+// get. Also keeps the waves within the displacement the ocean tiles are culled with.
+const MIN_WAVE_LENGTH: f32 = 6.;
+const MAX_WAVE_LENGTH: f32 = 64.;
+// Sum of steepness approaches the most a Gerstner sea can take without looping as the wind rises,
+// at wind speeds roughly following the Beaufort scale
+const MAX_TOTAL_STEEPNESS: f32 = 0.95;
+const STEEPNESS_WIND_SPEED: f32 = 3.7;
+// Angle from the wind direction, share of the total steepness and wavelength relative to the
+// dominant wave of each wave. Shorter waves are spread wider around the wind.
+const WIND_WAVES: [(f32, f32, f32); 4] = [
+    (0., 0.29, 1.),
+    (0.5, 0.26, 0.75),
+    (-0.45, 0.24, 0.5),
+    (0.9, 0.21, 0.375),
+];
+
+// Radians per second the phase of a wave advances, with the speed of deep water waves of its length
+pub fn phase_speed(wave: Vec4) -> f32 {
+    let k: f32 = 2. * PI / wave.w;
+    let c: f32 = (GRAVITY / k).sqrt();
+
+    k * c
+}
+
 // See: https://catlikecoding.com/unity/tutorials/flow/waves/
 // `wave`: Vec4 containing direction x, direction z, steepness, wave_length
-pub fn gerstner_wave(wave: Vec4, p: Vec3, time: f32) -> Vec3 {
+// `phase`: How far the wave has travelled, accumulated from its phase speed so that changing the
+// wave does not shift it by everything it travelled before
+pub fn gerstner_wave(wave: Vec4, p: Vec3, phase: f32) -> Vec3 {
     let steepness = wave.z;
     let wave_length = wave.w;
 
     let k: f32 = 2. * PI / wave_length;
-    let c: f32 = (GRAVITY / k).sqrt();
     let d: Vec2 = wave.xy().normalize();
-    let f: f32 = k * (d.dot(p.xz()) - c * time);
+    let f: f32 = k * d.dot(p.xz()) - phase;
     let a: f32 = steepness / k;
 
     Vec3::new(d.x * (a * f.cos()), a * f.sin(), d.y * (a * f.cos()))
@@ -24,15 +53,14 @@ pub fn gerstner_wave_tangent_binormal(
     p: Vec3,
     tangent: &mut Vec3,
     binormal: &mut Vec3,
-    time: f32,
+    phase: f32,
 ) -> Vec3 {
     let steepness = wave.z;
     let wave_length = wave.w;
 
     let k: f32 = 2. * PI / wave_length;
-    let c: f32 = (GRAVITY / k).sqrt();
     let d: Vec2 = wave.xy().normalize();
-    let f: f32 = k * (d.dot(p.xz()) - c * time);
+    let f: f32 = k * d.dot(p.xz()) - phase;
     let a: f32 = steepness / k;
 
     *tangent += Vec3::new(
@@ -54,21 +82,43 @@ pub fn gerstner_wave_tangent_binormal(
 pub fn wave_height<F>(
     point: Vec3,
     waves: [Vec4; 4],
-    time: f32,
+    phases: [f32; 4],
     sample_count: u8,
     next_position: F,
 ) -> f32
 where
-    F: Fn(Vec3, [Vec4; 4], f32) -> Vec3,
+    F: Fn(Vec3, [Vec4; 4], [f32; 4]) -> Vec3,
 {
     let mut sample_point = point;
     let mut displacement;
     for _i in 1..sample_count {
-        displacement = next_position(sample_point, waves, time);
+        displacement = next_position(sample_point, waves, phases);
         sample_point -= displacement - point;
     }
     // Do last sample outside loop to avoid extra calculation
-    displacement = next_position(sample_point, waves, time);
+    displacement = next_position(sample_point, waves, phases);
 
     displacement.y
 }
+
+// Direction, steepness and wavelength of each Gerstner wave raised by given wind, longer and steeper
+// waves running downwind as the wind picks up
+pub fn wind_waves(wind: Vec3) -> [Vec4; 4] {
+    let wind_speed = wind.xz().length();
+    let wind_direction = wind.xz().try_normalize().unwrap_or(Vec2::X);
+
+    let dominant_wave_length = (WAVE_LENGTH_PER_SQUARED_WIND_SPEED * wind_speed.powi(2))
+        .clamp(MIN_WAVE_LENGTH, MAX_WAVE_LENGTH);
+    let total_steepness = MAX_TOTAL_STEEPNESS * (1. - (-wind_speed / STEEPNESS_WIND_SPEED).exp());
+
+    WIND_WAVES.map(|(angle, steepness_share, wave_length_ratio)| {
+        let direction = Vec2::from_angle(angle).rotate(wind_direction);
+
+        Vec4::new(
+            direction.x,
+            direction.y,
+            total_steepness * steepness_share,
+            dominant_wave_length * wave_length_ratio,
+        )
+    })
+}
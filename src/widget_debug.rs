@@ -1,12 +1,12 @@
 mod systems;
 
-use crate::widget_debug::systems::{debug_buoys, debug_particle};
+use crate::widget_debug::systems::{debug_buoys, debug_hull_buoyancy, debug_particle};
 use bevy::prelude::*;
 
 pub struct WidgetDebugPlugin;
 
 impl Plugin for WidgetDebugPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (debug_buoys, debug_particle));
+        app.add_systems(Update, (debug_buoys, debug_hull_buoyancy, debug_particle));
     }
 }
@@ -1,4 +1,4 @@
-use crate::physics::components::{Buoy, Mass};
+use crate::physics::components::{Buoy, HullBuoyancy, Mass};
 use bevy::prelude::*;
 
 pub fn debug_buoys(buoy_query: Query<(&Buoy, &GlobalTransform)>, mut gizmos: Gizmos) {
@@ -11,6 +11,27 @@ pub fn debug_buoys(buoy_query: Query<(&Buoy, &GlobalTransform)>, mut gizmos: Giz
     }
 }
 
+// Outlines the hull voxels that are at least partly under water
+pub fn debug_hull_buoyancy(
+    hull_query: Query<(&HullBuoyancy, &GlobalTransform)>,
+    mut gizmos: Gizmos,
+) {
+    for (hull_buoyancy, global_transform) in &hull_query {
+        let (_, rotation, _) = global_transform.to_scale_rotation_translation();
+        for (voxel, water_height, _) in hull_buoyancy.voxels() {
+            let voxel_translation = global_transform.transform_point(voxel);
+            if voxel_translation.y - hull_buoyancy.voxel_size / 2. < water_height {
+                gizmos.cuboid(
+                    Transform::from_translation(voxel_translation)
+                        .with_rotation(rotation)
+                        .with_scale(Vec3::splat(hull_buoyancy.voxel_size)),
+                    Color::CYAN,
+                )
+            }
+        }
+    }
+}
+
 pub fn debug_particle(particle_query: Query<(&Mass, &GlobalTransform)>, mut gizmos: Gizmos) {
     for (mass, global_transform) in &particle_query {
         let (_, rotation, translation) = global_transform.to_scale_rotation_translation();
@@ -1,12 +1,57 @@
-use crate::wind::resources::Wind;
+use crate::desync_report::ChecksumReportApp;
+use crate::physics::systems::{update_aerodynamic_force, update_linear_drag_force};
+use crate::player::systems::update_sail_trim_ratio;
+use crate::wind::resources::{checksum_wind, Wind, WindConditions, WindVariation};
+use crate::wind::systems::update_wind;
 use bevy::prelude::*;
+use bevy_ggrs::GgrsSchedule;
 
 pub mod resources;
+pub mod systems;
+
+const MEAN_WIND: Vec3 = Vec3::new(6., 0., 0.);
+
+// Same seed on every peer, so that everyone sails in the same gusts
+const WIND_SEED: u32 = 0x5ea_5a11;
+
+const WIND_VEER_ANGLE: f32 = 0.35;
+const WIND_VEER_PERIOD: f32 = 90.;
+const WIND_GUST_STRENGTH: f32 = 0.3;
+const WIND_GUST_PERIOD: f32 = 8.;
+const WIND_SHADOW_STRENGTH: f32 = 0.5;
+
+// Extents of the shadow downwind of a sail, fading out towards its end and edges
+pub const WIND_SHADOW_LENGTH: f32 = 24.;
+pub const WIND_SHADOW_WIDTH: f32 = 4.;
 
 pub struct WindPlugin;
 
 impl Plugin for WindPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Wind(Vec3::new(6., 0., 0.)));
+        let wind_conditions = WindConditions {
+            mean: MEAN_WIND,
+            variation: WindVariation {
+                seed: WIND_SEED,
+                veer_angle: WIND_VEER_ANGLE,
+                veer_period: WIND_VEER_PERIOD,
+                gust_strength: WIND_GUST_STRENGTH,
+                gust_period: WIND_GUST_PERIOD,
+                shadow_strength: WIND_SHADOW_STRENGTH,
+            },
+        };
+
+        app.insert_resource(Wind(wind_conditions.wind_at(0.)));
+        app.insert_resource(wind_conditions);
+
+        // Evaluated from the frame being simulated, so nothing needs restoring on rollback
+        app.checksum_and_report_resource::<Wind>(checksum_wind);
+
+        app.add_systems(
+            GgrsSchedule,
+            update_wind
+                .before(update_sail_trim_ratio)
+                .before(update_aerodynamic_force)
+                .before(update_linear_drag_force),
+        );
     }
 }
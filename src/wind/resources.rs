@@ -1,4 +1,97 @@
+use crate::utils::hash::hash_vec3;
+use crate::utils::noise::value_noise;
+use crate::wind::{WIND_SHADOW_LENGTH, WIND_SHADOW_WIDTH};
+use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Resource)]
+// Wind of the current frame, blowing the same everywhere apart from the shadows of sails
+#[derive(Resource, Clone, Copy, Default, Debug)]
 pub struct Wind(pub Vec3);
+
+pub fn checksum_wind(value: &Wind) -> u64 {
+    hash_vec3(value.0)
+}
+
+// How the wind strays from its mean. The default is a steady wind, which is what replays recorded
+// before the wind varied were sailed in.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct WindVariation {
+    pub seed: u32,
+    // Largest angle in radians the wind veers or backs away from its mean direction
+    pub veer_angle: f32,
+    // Seconds between independent wind directions
+    pub veer_period: f32,
+    // Largest change in wind speed in gusts and lulls, as a fraction of the mean speed
+    pub gust_strength: f32,
+    // Seconds between independent gusts
+    pub gust_period: f32,
+    // Fraction of the wind taken out right behind a sail, 0 disabling wind shadows
+    pub shadow_strength: f32,
+}
+
+// Everything the wind of a match is evaluated from. Only depends on the simulated time, so that
+// every peer computes the same wind for every frame, also when resimulating.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct WindConditions {
+    pub mean: Vec3,
+    pub variation: WindVariation,
+}
+
+impl WindConditions {
+    pub fn wind_at(&self, elapsed_seconds: f32) -> Vec3 {
+        let variation = &self.variation;
+
+        let veer = if variation.veer_period > 0. {
+            variation.veer_angle
+                * value_noise(variation.seed, elapsed_seconds / variation.veer_period)
+        } else {
+            0.
+        };
+
+        // Gusts layered with quicker puffs, which read as more natural than a single wave
+        let gust = if variation.gust_period > 0. {
+            let seed = variation.seed.wrapping_add(1);
+            0.7 * value_noise(seed, elapsed_seconds / variation.gust_period)
+                + 0.3 * value_noise(seed, 3. * elapsed_seconds / variation.gust_period)
+        } else {
+            0.
+        };
+
+        Quat::from_rotation_y(veer) * self.mean * (1. + variation.gust_strength * gust)
+    }
+
+    // Fraction of the wind left at given position, in the shadows of sails at given positions.
+    // The deepest shadow counts, so the order of the sails does not matter.
+    pub fn shadowing(
+        &self,
+        wind: Vec3,
+        position: Vec3,
+        sail_positions: impl Iterator<Item = Vec3>,
+    ) -> f32 {
+        let strength = self.variation.shadow_strength;
+        if strength <= 0. {
+            return 1.;
+        }
+
+        let wind_direction = wind.xz().normalize_or_zero();
+        let deepest_shadow = sail_positions
+            .map(|sail_position| {
+                let offset = (position - sail_position).xz();
+                let downwind = offset.dot(wind_direction);
+                let crosswind = offset.perp_dot(wind_direction).abs();
+
+                if downwind <= f32::EPSILON
+                    || downwind >= WIND_SHADOW_LENGTH
+                    || crosswind >= WIND_SHADOW_WIDTH
+                {
+                    return 0.;
+                }
+
+                (1. - downwind / WIND_SHADOW_LENGTH) * (1. - crosswind / WIND_SHADOW_WIDTH)
+            })
+            .fold(0., f32::max);
+
+        1. - strength * deepest_shadow
+    }
+}
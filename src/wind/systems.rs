@@ -0,0 +1,11 @@
+use crate::simulation::resources::SimulationClock;
+use crate::wind::resources::{Wind, WindConditions};
+use bevy::prelude::*;
+
+pub fn update_wind(
+    mut wind: ResMut<Wind>,
+    wind_conditions: Res<WindConditions>,
+    clock: Res<SimulationClock>,
+) {
+    wind.0 = wind_conditions.wind_at(clock.elapsed_seconds());
+}